@@ -0,0 +1,325 @@
+use std::error;
+use std::fmt::{self, Display};
+use std::io;
+use std::ops::Range;
+use std::str::FromStr;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Return an `Error::InvalidPuzzle` built from a format string.
+// Originally stolen from burntsushi's AOC day 3 solution
+#[macro_export]
+macro_rules! err {
+    ($($tt:tt)*) => { Err($crate::Error::InvalidPuzzle(format!($($tt)*))) }
+}
+
+/// A piece of the puzzle input that could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number, 0 if the error has not been attributed to a line
+    /// of the input yet
+    pub line: usize,
+    /// 1-based byte column of the start of `text`
+    pub column: usize,
+    /// The offending text
+    pub text: String,
+    pub reason: String,
+    /// The complete line the error was found on, if known
+    pub source_line: Option<String>,
+}
+
+impl ParseError {
+    /// Byte columns covered by the offending text, end exclusive.
+    pub fn span(&self) -> Range<usize> {
+        self.column..self.column + self.text.len().max(1)
+    }
+
+    /// 1-based column of the first character of `text` in `source`, as shown
+    /// to the reader.
+    fn char_column(&self, source: &str) -> usize {
+        let start = self.column - 1;
+        let chars = source.char_indices().take_while(|&(idx, _)| idx < start).count();
+        chars + start.saturating_sub(source.len()) + 1
+    }
+
+    fn render(&self, f: &mut fmt::Formatter, file: &str, gutter: usize) -> fmt::Result {
+        let source = match (self.line, &self.source_line) {
+            (0, _) | (_, None) =>
+                return write!(f, "\n{:>g$}--> {}: {} (at column {}: {:?})",
+                                "", file, self.reason, self.column, self.text, g = gutter),
+            (_, Some(source)) => source,
+        };
+        let column = self.char_column(source);
+        let indent: String = source.chars()
+            .take(column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        write!(f, "\n{:>g$}--> {}:{}:{}", "", file, self.line, column, g = gutter)?;
+        write!(f, "\n{:>g$} |", "", g = gutter)?;
+        write!(f, "\n{:>g$} | {}", self.line, source, g = gutter)?;
+        write!(f, "\n{:>g$} | {}{} {}", "", indent, "^".repeat(self.text.chars().count().max(1)),
+               self.reason, g = gutter)
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// Parts of the puzzle input could not be parsed.
+    Parse {
+        /// Name of the input file, if known
+        file: Option<String>,
+        errors: Vec<ParseError>,
+    },
+    /// The puzzle input could not be read.
+    Io(io::Error),
+    /// The input is well-formed, but does not describe a solvable puzzle.
+    InvalidPuzzle(String),
+}
+
+impl Error {
+    /// Parse error for `text`, found at the zero-based byte offset `start` of
+    /// the line that is being parsed.
+    pub fn parse<S: Into<String>>(start: usize, text: &str, reason: S) -> Error {
+        Error::Parse {
+            file: None,
+            errors: vec![ParseError {
+                line: 0,
+                column: start + 1,
+                text: text.to_string(),
+                reason: reason.into(),
+                source_line: None,
+            }],
+        }
+    }
+
+    /// Attribute a parse error to the given (1-based) line of the input.
+    pub fn at_line(mut self, line: usize, source_line: &str) -> Error {
+        if let Error::Parse { ref mut errors, .. } = self {
+            for error in errors {
+                error.line = line;
+                error.source_line = Some(source_line.to_string());
+            }
+        }
+        self
+    }
+
+    /// Shift the column of a parse error that was raised on a substring
+    /// starting at the zero-based byte offset `start` of its line.
+    pub fn offset(mut self, start: usize) -> Error {
+        if let Error::Parse { ref mut errors, .. } = self {
+            for error in errors {
+                error.column += start;
+            }
+        }
+        self
+    }
+
+    /// Attribute a parse error to the file it was read from.
+    pub fn in_file(mut self, name: &str) -> Error {
+        if let Error::Parse { ref mut file, .. } = self {
+            *file = Some(name.to_string());
+        }
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse { file, errors } => {
+                let file = file.as_ref().map_or("<input>", |f| f.as_str());
+                match errors.len() {
+                    1 => write!(f, "failed to parse {}", file)?,
+                    n => write!(f, "failed to parse {}: {} errors", file, n)?,
+                }
+                let gutter = errors.iter()
+                    .map(|e| e.line.to_string().len())
+                    .max().unwrap_or(1);
+                for error in errors {
+                    error.render(f, file, gutter)?;
+                }
+                Ok(())
+            },
+            Error::Io(e) => write!(f, "failed to read input: {}", e),
+            Error::InvalidPuzzle(msg) => write!(f, "invalid puzzle: {}", msg),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+/// Parse a single token that starts at the zero-based byte offset `start` of
+/// its line.
+pub fn parse_token<T>(token: &str, start: usize) -> Result<T>
+    where T: FromStr, T::Err: Display
+{
+    token.parse().map_err(|e: T::Err| Error::parse(start, token, e.to_string()))
+}
+
+/// Collect the parsed items, or all of the parse errors if there were any.
+///
+/// Errors other than parse errors are returned immediately.
+pub fn collect_errors<T, I>(results: I) -> Result<Vec<T>>
+    where I: IntoIterator<Item=Result<T>>
+{
+    let mut items = Vec::new();
+    let mut parse_errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(item) => items.push(item),
+            Err(Error::Parse { errors, .. }) => parse_errors.extend(errors),
+            Err(e) => return Err(e),
+        }
+    }
+    if parse_errors.is_empty() {
+        Ok(items)
+    } else {
+        Err(Error::Parse { file: None, errors: parse_errors })
+    }
+}
+
+/// Parse every non-blank line of the input, attributing errors to their line
+/// number and reporting all lines that failed to parse.
+pub fn parse_lines<T, F>(input: &str, mut parse: F) -> Result<Vec<T>>
+    where F: FnMut(&str) -> Result<T>
+{
+    collect_errors(input.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| parse(line).map_err(|e| e.at_line(idx + 1, line))))
+}
+
+/// Whitespace-separated tokens of a line, with their zero-based byte offsets.
+pub fn tokens(line: &str) -> impl Iterator<Item=(usize, &str)> {
+    let base = line.as_ptr() as usize;
+    line.split_whitespace()
+        .map(move |tok| (tok.as_ptr() as usize - base, tok))
+}
+
+/// Match the tokens of `line` against `grammar`, where `Some` is a keyword
+/// and `None` a value described by `value`, e.g. "a number".
+///
+/// Returns the tokens of the values along with their offsets, or an error
+/// for the first token that does not match, a missing token or text after
+/// the last token, which is called "unexpected text after `what`".
+pub fn match_tokens<'a>(line: &'a str, grammar: &[Option<&str>], value: &str, what: &str)
+    -> Result<Vec<(usize, &'a str)>>
+{
+    let mut parts = tokens(line);
+    let mut values = Vec::new();
+    for expected in grammar {
+        let (start, token) = match (parts.next(), expected) {
+            (Some(part), _) => part,
+            (None, Some(keyword)) =>
+                return Err(Error::parse(line.len(), "", format!("expected `{}`", keyword))),
+            (None, None) =>
+                return Err(Error::parse(line.len(), "", format!("expected {}", value))),
+        };
+        match expected {
+            Some(keyword) if token != *keyword =>
+                return Err(Error::parse(start, token, format!("expected `{}`", keyword))),
+            Some(_) => (),
+            None => values.push((start, token)),
+        }
+    }
+    if let Some((start, _)) = parts.next() {
+        return Err(Error::parse(start, line[start..].trim_end(),
+                                format!("unexpected text after {}", what)));
+    }
+    Ok(values)
+}
+
+/// A single day's puzzle.
+///
+/// The input is parsed once, after which both parts can be solved from the
+/// parsed representation as often as needed.
+pub trait Solution: Sized {
+    type Part1Answer: Display;
+    type Part2Answer: Display;
+
+    fn parse(input: &str) -> Result<Self>;
+
+    fn part1(&self) -> Result<Self::Part1Answer>;
+
+    fn part2(&self) -> Result<Self::Part2Answer>;
+}
+
+
+/// Helpers for the tests of the puzzle parsers.
+pub mod test_support {
+    use std::str::FromStr;
+
+    use crate::Error;
+
+    /// Byte column and reason of the first parse error for `line`, panicking
+    /// if it parses or fails with another error.
+    pub fn parse_error<T: FromStr<Err=Error>>(line: &str) -> (usize, String) {
+        match line.parse::<T>() {
+            Err(Error::Parse { errors, .. }) => (errors[0].column, errors[0].reason.clone()),
+            Err(e) => panic!("expected a parse error, got {}", e),
+            Ok(_)  => panic!("expected a parse error for {:?}", line),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_all_bad_lines() {
+        let result: Result<Vec<u32>> = parse_lines("1\nx\n3\n\n-4\n", |l| parse_token(l, 0));
+        match result {
+            Err(Error::Parse { errors, .. }) => {
+                let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+                assert_eq!(vec![2, 5], lines);
+            },
+            other => panic!("expected parse errors, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn renders_caret_under_token() {
+        let line = "#2 @ 3,x: 4x4";
+        let err = parse_token::<u32>("x", 7).unwrap_err()
+            .at_line(12, line)
+            .in_file("input.txt");
+        let expected = "failed to parse input.txt
+  --> input.txt:12:8
+   |
+12 | #2 @ 3,x: 4x4
+   |        ^ invalid digit found in string";
+        assert_eq!(expected, err.to_string());
+
+        let line = "#é @ 1,x: 4x4";
+        let err = parse_token::<u32>("x", 8).unwrap_err()
+            .at_line(1, line)
+            .in_file("input.txt");
+        let expected = "failed to parse input.txt
+ --> input.txt:1:8
+  |
+1 | #é @ 1,x: 4x4
+  |        ^ invalid digit found in string";
+        assert_eq!(expected, err.to_string());
+
+        // Byte offsets add up the same whichever order the helpers run in
+        let err = parse_token::<u32>("x", 2).unwrap_err()
+            .at_line(1, line)
+            .offset(6)
+            .in_file("input.txt");
+        assert_eq!(expected, err.to_string());
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use aoc_common::{Error, Result};
+use clap::Subcommand;
+use day08::Node;
+
+use crate::{parse, read_input};
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// List metadata entries referencing missing children and numbers left
+    /// after the root node, by their 1-based position in the input
+    Check {
+        /// Puzzle input, `-` for stdin [default: day08/input.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Fail if there is anything to report
+        #[arg(long)]
+        strict: bool,
+    },
+}
+
+pub fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Check { input, strict } => {
+            let input = read_input(8, input)?;
+            // Fail on numbers that do not describe a tree at all
+            let _: Node = parse(&input)?;
+            let numbers = day08::numbers(&input.text)?;
+            let issues = Node::validate(numbers);
+            for issue in &issues {
+                println!("number {:>6}: {}", issue.position() + 1, issue);
+            }
+            match issues.len() {
+                0           => println!("No issues found"),
+                n if strict => return Err(Error::InvalidPuzzle(
+                    format!("{} issue(s) found in {}", n, input.name))),
+                n           => println!(
+                    "{} issue(s) found, references to missing children count as zero and \
+                     numbers after the root node are ignored", n),
+            }
+            Ok(())
+        },
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use aoc_common::{Result, Solution};
+use clap::{Parser, Subcommand};
+
+mod license;
+mod marbles;
+mod steps;
+
+const NUM_DAYS: u8 = 10;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2018 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve the puzzle for a single day
+    Run {
+        /// Day of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=NUM_DAYS as i64))]
+        day: u8,
+        /// Only solve this part of the puzzle
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input, `-` for stdin [default: dayNN/input.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Show the progress of long-running puzzles on stderr
+        #[arg(long)]
+        progress: bool,
+    },
+    /// Solve every day with its bundled puzzle input
+    All {
+        /// Show the progress of long-running puzzles on stderr
+        #[arg(long)]
+        progress: bool,
+    },
+    /// Tools for the day 7 step scheduler
+    Day07 {
+        #[command(subcommand)]
+        command: steps::Command,
+    },
+    /// Tools for the day 8 license tree
+    Day08 {
+        #[command(subcommand)]
+        command: license::Command,
+    },
+    /// Tools for the day 9 marble game
+    Day09 {
+        #[command(subcommand)]
+        command: marbles::Command,
+    },
+}
+
+/// Puzzle input along with a name to refer to it in diagnostics
+struct Input {
+    name: String,
+    text: String,
+}
+
+fn read_input(day: u8, path: Option<PathBuf>) -> Result<Input> {
+    let path = path.unwrap_or_else(|| default_input(day));
+    if path.to_str() == Some("-") {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        Ok(Input { name: "<stdin>".to_string(), text })
+    } else {
+        let name = path.display().to_string();
+        let text = fs::read_to_string(&path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", name, e)))?;
+        Ok(Input { name, text })
+    }
+}
+
+/// Input bundled with the day's crate, found from the workspace the runner
+/// was built in so that it works from any directory.
+fn default_input(day: u8) -> PathBuf {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    workspace.join(format!("day{:02}", day)).join("input.txt")
+}
+
+fn print_answer<T: Display>(part: u8, answer: T) {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("Part {}:\n{}", part, answer);
+    } else {
+        println!("Part {}: {}", part, answer);
+    }
+}
+
+fn parse<S: Solution>(input: &Input) -> Result<S> {
+    S::parse(&input.text).map_err(|e| e.in_file(&input.name))
+}
+
+fn solve<S: Solution>(input: &Input, parts: &[u8]) -> Result<()> {
+    print_parts(&parse::<S>(input)?, parts)
+}
+
+fn print_parts<S: Solution>(solution: &S, parts: &[u8]) -> Result<()> {
+    for &part in parts {
+        match part {
+            1 => print_answer(part, solution.part1()?),
+            _ => print_answer(part, solution.part2()?),
+        }
+    }
+    Ok(())
+}
+
+fn solve_day(day: u8, input: &Input, parts: &[u8], progress: bool) -> Result<()> {
+    match day {
+        1 => solve::<day01::Frequencies>(input, parts),
+        2 => solve::<day02::BoxIds>(input, parts),
+        3 => solve::<day03::Claims>(input, parts),
+        4 => solve::<day04::GuardLog>(input, parts),
+        5 => solve::<day05::Polymer>(input, parts),
+        6 => solve::<day06::Coordinates>(input, parts),
+        7 => solve::<day07::Instructions>(input, parts),
+        8 => solve::<day08::Node>(input, parts),
+        9 => {
+            let mut games: day09::Games = parse(input)?;
+            games.set_show_progress(progress);
+            print_parts(&games, parts)
+        },
+        10 => solve::<day10::Message>(input, parts),
+        _ => unreachable!("days are validated by the argument parser"),
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    match cli.command {
+        Command::Run { day, part, input, progress } => {
+            let input = read_input(day, input)?;
+            let parts = match part {
+                Some(part) => vec![part],
+                None       => vec![1, 2],
+            };
+            solve_day(day, &input, &parts, progress)
+        },
+        Command::All { progress } => {
+            for day in 1..=NUM_DAYS {
+                println!("Day {}:", day);
+                solve_day(day, &read_input(day, None)?, &[1, 2], progress)?;
+            }
+            Ok(())
+        },
+        Command::Day07 { command } => steps::run(command),
+        Command::Day08 { command } => license::run(command),
+        Command::Day09 { command } => marbles::run(command),
+    }
+}
+
+fn main() {
+    if let Err(e) = run(Cli::parse()) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
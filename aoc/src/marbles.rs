@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+use std::time::Instant;
+
+use aoc_common::{Error, Result};
+use clap::Subcommand;
+use day09::{Games, MarbleGame};
+
+use crate::{parse, read_input};
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Play every game of the input in parallel and time them
+    Batch {
+        /// One game per line, `-` for stdin [default: day09/input.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Multiply the value of the last marble of every game by this
+        #[arg(long, default_value_t = 1)]
+        factor: usize,
+        /// Number of threads to play the games on [default: number of CPUs]
+        #[arg(long)]
+        threads: Option<usize>,
+    },
+    /// Print the circle after every round, like the puzzle text does
+    Trace {
+        /// One game per line, `-` for stdin [default: day09/input.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Line of the game to trace
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        game: u64,
+        /// Number of rounds to trace
+        #[arg(long, default_value_t = 25)]
+        rounds: usize,
+    },
+}
+
+pub fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Batch { input, factor, threads } => {
+            let games: Games = parse(&read_input(9, input)?)?;
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads.unwrap_or(0))
+                .build()
+                .map_err(|e| Error::InvalidPuzzle(format!("Could not start threads: {}", e)))?;
+            let started = Instant::now();
+            let results = pool.install(|| games.play(factor));
+            let elapsed = started.elapsed();
+            println!("{:>8}  {:>12}  {:>12}  {:>10}", "players", "last marble", "high score", "time");
+            for result in &results {
+                println!("{:>8}  {:>12}  {:>12}  {:>8.1}ms", result.players, result.num_marbles,
+                         result.high_score, result.elapsed.as_secs_f64() * 1000.0);
+            }
+            let total: f64 = results.iter().map(|r| r.elapsed.as_secs_f64()).sum();
+            println!("{} games played in {:.1}ms on {} thread(s) ({:.1}ms of playing time)",
+                     results.len(), elapsed.as_secs_f64() * 1000.0,
+                     pool.current_num_threads(), total * 1000.0);
+            Ok(())
+        },
+        Command::Trace { input, game, rounds } => {
+            let games: Games = parse(&read_input(9, input)?)?;
+            let &(players, num_marbles) = match games.games().get(game as usize - 1) {
+                Some(game) => game,
+                None       => return Err(Error::InvalidPuzzle(
+                    format!("There are only {} games", games.games().len()))),
+            };
+            MarbleGame::new(players, num_marbles).trace(rounds, |line| println!("{}", line));
+            Ok(())
+        },
+    }
+}
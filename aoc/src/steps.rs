@@ -0,0 +1,401 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_common::{Error, Result};
+use clap::{Args, Subcommand, ValueEnum};
+use day07::{from_json_lines, to_json_lines, Event, FastestFirst, Highlight, Instructions, LetterCost, LowestId, RoundRobin, Schedule, Worker,
+            WorkerPolicy, BASE_DURATION, NUM_WORKERS};
+use serde::Deserialize;
+
+use crate::{parse, read_input};
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Show how the workers from part 2 work through the steps
+    Schedule {
+        /// Puzzle input, `-` for stdin [default: day07/input.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[command(flatten)]
+        pool: PoolArgs,
+        #[arg(long, value_enum, default_value_t = ScheduleFormat::Gantt)]
+        format: ScheduleFormat,
+        /// Number of columns for the time axis of the chart
+        #[arg(long, default_value_t = 72)]
+        width: usize,
+    },
+    /// Export the dependencies between the steps as a graph
+    Graph {
+        /// Puzzle input, `-` for stdin [default: day07/input.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[command(flatten)]
+        pool: PoolArgs,
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+        #[arg(long, value_enum, default_value_t = GraphColour::None)]
+        colour: GraphColour,
+    },
+    /// Print what happens to the workers from part 2 as JSON Lines
+    Events {
+        /// Puzzle input, `-` for stdin [default: day07/input.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[command(flatten)]
+        pool: PoolArgs,
+    },
+    /// Check that a JSON Lines event log respects the dependencies and the
+    /// worker pool
+    Replay {
+        /// Event log as written by `events`, `-` for stdin
+        log: PathBuf,
+        /// Puzzle input [default: day07/input.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[command(flatten)]
+        pool: PoolArgs,
+    },
+    /// Check the steps for dependency cycles and list the isolated steps,
+    /// which neither depend on nor are needed by any other step
+    Check {
+        /// Puzzle input, `-` for stdin [default: day07/input.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Report the time it takes 1..N identical workers to finish all steps
+    Sweep {
+        /// Puzzle input, `-` for stdin [default: day07/input.txt]
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[command(flatten)]
+        durations: DurationArgs,
+        /// Largest number of workers to try [default: number of steps]
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        max_workers: Option<u64>,
+    },
+}
+
+/// Worker pool, taken from the command line, then the config file, then the
+/// puzzle's defaults.
+#[derive(Args)]
+pub struct PoolArgs {
+    /// Number of identical workers [default: 5]
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    workers: Option<u64>,
+    /// Seconds every step takes in addition to its letter cost [default: 60]
+    #[arg(long)]
+    base_duration: Option<u32>,
+    /// How to choose among the idle workers [default: lowest-id]
+    #[arg(long, value_enum)]
+    policy: Option<Policy>,
+    /// TOML file with a `[day07]` table for `workers`, `base_duration` and
+    /// `policy`, `[[day07.worker]]` entries with a `speed` and `skills` and a
+    /// `[day07.skills]` table with the skills every step needs
+    #[arg(long)]
+    config: Option<PathBuf>,
+}
+
+/// Step durations for a sweep over identical workers, taken from the command
+/// line, then the config file, then the puzzle's defaults.
+#[derive(Args)]
+pub struct DurationArgs {
+    /// Seconds every step takes in addition to its letter cost [default: 60]
+    #[arg(long)]
+    base_duration: Option<u32>,
+    /// TOML file with a `[day07]` table for `base_duration`, its `workers` and
+    /// `policy` make no difference to a sweep
+    #[arg(long)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Policy {
+    /// Worker with the lowest number
+    LowestId,
+    /// Fastest worker, then the one with the lowest number
+    FastestFirst,
+    /// Take turns among the workers
+    RoundRobin,
+}
+
+#[derive(Default, Deserialize)]
+struct Config {
+    #[serde(default)]
+    day07: PoolConfig,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PoolConfig {
+    workers: Option<usize>,
+    base_duration: Option<u32>,
+    policy: Option<Policy>,
+    #[serde(default, rename = "worker")]
+    crew: Vec<WorkerConfig>,
+    #[serde(default)]
+    skills: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkerConfig {
+    #[serde(default = "normal_speed")]
+    speed: f64,
+    #[serde(default)]
+    skills: Vec<String>,
+}
+
+fn normal_speed() -> f64 {
+    1.0
+}
+
+/// Resolved worker pool along with the skills every step needs.
+struct Pool {
+    workers: Vec<Worker>,
+    base_duration: u32,
+    policy: Policy,
+    skills: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ScheduleFormat {
+    /// Text chart of the workers along with the critical path
+    Gantt,
+    /// One `worker,step,start,end` row per step
+    Csv,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT digraph
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum GraphColour {
+    /// Leave the steps uncoloured
+    None,
+    /// Shade the steps by their position in the order from part 1
+    Order,
+    /// Colour the steps by the worker that completes them in part 2
+    Worker,
+}
+
+impl PoolArgs {
+    /// Workers and base duration to use.
+    ///
+    /// Passing `--workers` replaces the workers from the config file with
+    /// identical ones.
+    fn resolve(&self) -> Result<Pool> {
+        let config = match &self.config {
+            Some(path) => read_config(path)?.day07,
+            None       => PoolConfig::default(),
+        };
+        self.resolve_with(config)
+    }
+
+    fn resolve_with(&self, config: PoolConfig) -> Result<Pool> {
+        if config.workers.is_some() && !config.crew.is_empty() {
+            return Err(Error::InvalidPuzzle(
+                "The config file sets both `workers` and `[[day07.worker]]`, keep only one \
+                 of them".to_string()));
+        }
+        let workers = match (self.workers, config.workers) {
+            (Some(count), _) => vec![Worker::new(); count as usize],
+            (None, _) if !config.crew.is_empty() => config.crew.iter()
+                .map(|worker| Worker {
+                    speed: worker.speed,
+                    skills: worker.skills.iter().cloned().collect(),
+                })
+                .collect(),
+            (None, count) => vec![Worker::new(); count.unwrap_or(NUM_WORKERS)],
+        };
+        Ok(Pool {
+            workers,
+            base_duration: self.base_duration.or(config.base_duration).unwrap_or(BASE_DURATION),
+            policy: self.policy.or(config.policy).unwrap_or(Policy::LowestId),
+            skills: config.skills,
+        })
+    }
+}
+
+impl Pool {
+    /// Mark the steps with the skills they need.
+    fn require_skills(&self, instructions: &mut Instructions) {
+        let scheduler = instructions.scheduler_mut();
+        for (step, skills) in &self.skills {
+            for skill in skills {
+                scheduler.require_skill(step.clone(), skill);
+            }
+        }
+    }
+
+    /// Schedule for the pool after marking the steps with their skills.
+    fn schedule(&self, instructions: &mut Instructions) -> Result<Schedule<String>> {
+        self.require_skills(instructions);
+        let mut policy: Box<dyn WorkerPolicy> = match self.policy {
+            Policy::LowestId     => Box::new(LowestId),
+            Policy::FastestFirst => Box::new(FastestFirst),
+            Policy::RoundRobin   => Box::new(RoundRobin::default()),
+        };
+        instructions.schedule_with(&self.workers, self.base_duration, policy.as_mut())
+    }
+}
+
+impl DurationArgs {
+    /// Base duration to use, rejecting config files that describe workers a
+    /// sweep cannot use.
+    fn resolve(&self) -> Result<u32> {
+        let config = match &self.config {
+            Some(path) => read_config(path)?.day07,
+            None       => PoolConfig::default(),
+        };
+        if !config.crew.is_empty() || !config.skills.is_empty() {
+            return Err(Error::InvalidPuzzle(
+                "A sweep uses identical workers without skills, remove `[[day07.worker]]` \
+                 and `[day07.skills]` from the config file".to_string()));
+        }
+        Ok(self.base_duration.or(config.base_duration).unwrap_or(BASE_DURATION))
+    }
+}
+
+fn read_config(path: &Path) -> Result<Config> {
+    let name = path.display().to_string();
+    let text = fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", name, e)))?;
+    toml::from_str(&text).map_err(|e| {
+        let span = e.span().unwrap_or(0..0);
+        let line_start = text[..span.start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = text[span.start..].find('\n').map_or(text.len(), |idx| span.start + idx);
+        let line = text[..span.start].matches('\n').count() + 1;
+        let end = span.end.min(line_end).max(span.start);
+        Error::parse(span.start - line_start, &text[span.start..end], e.message())
+            .at_line(line, &text[line_start..line_end])
+            .in_file(&name)
+    })
+}
+
+pub fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Schedule { input, pool, format, width } => {
+            let mut instructions: Instructions = parse(&read_input(7, input)?)?;
+            let schedule = pool.resolve()?.schedule(&mut instructions)?;
+            match format {
+                ScheduleFormat::Csv => print!("{}", schedule.to_csv()),
+                ScheduleFormat::Gantt => {
+                    print!("{}", schedule.gantt(width));
+                    println!("Critical path ({} seconds): {}",
+                             schedule.critical_duration, schedule.critical_path.join(" -> "));
+                    println!("All steps finished after {} seconds", schedule.duration);
+                },
+            }
+            Ok(())
+        },
+        Command::Graph { input, pool, format, colour } => {
+            let mut instructions: Instructions = parse(&read_input(7, input)?)?;
+            let (order, schedule);
+            let highlight = match colour {
+                GraphColour::None => Highlight::None,
+                GraphColour::Order => {
+                    order = instructions.scheduler().order()?;
+                    Highlight::Order(&order)
+                },
+                GraphColour::Worker => {
+                    schedule = pool.resolve()?.schedule(&mut instructions)?;
+                    Highlight::Worker(&schedule)
+                },
+            };
+            let scheduler = instructions.scheduler();
+            match format {
+                GraphFormat::Dot => print!("{}", scheduler.to_dot(&highlight)),
+                GraphFormat::Mermaid => print!("{}", scheduler.to_mermaid(&highlight)),
+            }
+            Ok(())
+        },
+        Command::Events { input, pool } => {
+            let mut instructions: Instructions = parse(&read_input(7, input)?)?;
+            let schedule = pool.resolve()?.schedule(&mut instructions)?;
+            print!("{}", to_json_lines(&schedule.events));
+            Ok(())
+        },
+        Command::Replay { log, input, pool } => {
+            let stdin = Path::new("-");
+            if log == stdin && input.as_deref() == Some(stdin) {
+                return Err(Error::InvalidPuzzle(
+                    "The event log and the puzzle input cannot both be read from stdin".to_string()));
+            }
+            let log = read_input(7, Some(log))?;
+            let mut instructions: Instructions = parse(&read_input(7, input)?)?;
+            let events: Vec<Event<String>> = from_json_lines(&log.text)
+                .map_err(|e| e.in_file(&log.name))?;
+            let pool = pool.resolve()?;
+            pool.require_skills(&mut instructions);
+            instructions.scheduler().replay(&events, &pool.workers)?;
+            let ticks = events.last().map_or(0, |event| event.tick);
+            println!("{} events replayed, all steps finished after {} seconds", events.len(), ticks);
+            Ok(())
+        },
+        Command::Check { input } => {
+            let instructions: Instructions = parse(&read_input(7, input)?)?;
+            let scheduler = instructions.scheduler();
+            scheduler.validate()?;
+            println!("No dependency cycles found");
+            let isolated: Vec<String> = scheduler.isolated_steps().iter()
+                .map(|step| step.to_string())
+                .collect();
+            match isolated.len() {
+                0 => println!("No isolated steps"),
+                n => println!("{} isolated step(s): {}", n, isolated.join(", ")),
+            }
+            Ok(())
+        },
+        Command::Sweep { input, durations, max_workers } => {
+            let instructions: Instructions = parse(&read_input(7, input)?)?;
+            let base_duration = durations.resolve()?;
+            let scheduler = instructions.scheduler();
+            let max_workers = match max_workers {
+                Some(max_workers) => max_workers as usize,
+                None              => scheduler.steps().count().max(1),
+            };
+            let cost = LetterCost { base: base_duration };
+            let durations = scheduler.sweep(max_workers, &cost)?;
+            let (_, critical_duration) = scheduler.critical_path(&cost)?;
+            println!("workers  seconds  speedup");
+            for (idx, duration) in durations.iter().enumerate() {
+                let speedup = durations[0] as f64 / (*duration).max(1) as f64;
+                println!("{:>7}  {:>7}  {:>6.2}x", idx + 1, duration, speedup);
+            }
+            let best = durations.iter().min().cloned().unwrap_or(0);
+            if let Some(enough) = durations.iter().position(|&d| d == best) {
+                println!("No gain beyond {} worker(s): {} seconds (critical path: {} seconds)",
+                         enough + 1, best, critical_duration);
+            }
+            Ok(())
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool_config(text: &str) -> PoolConfig {
+        toml::from_str::<Config>(text).unwrap().day07
+    }
+
+    #[test]
+    fn rejects_worker_count_along_with_crew() {
+        let args = PoolArgs { workers: None, base_duration: None, policy: None, config: None };
+        let both = pool_config("[day07]\nworkers = 3\n[[day07.worker]]\nspeed = 2.0\n");
+        assert!(args.resolve_with(both).is_err());
+        let crew = pool_config("[[day07.worker]]\nspeed = 2.0\n[[day07.worker]]\n");
+        assert_eq!(2, args.resolve_with(crew).unwrap().workers.len());
+        let count = pool_config("[day07]\nworkers = 3\n");
+        assert_eq!(3, args.resolve_with(count).unwrap().workers.len());
+    }
+}
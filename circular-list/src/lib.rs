@@ -0,0 +1,453 @@
+//! A circular doubly-linked list backed by a `Vec`, with a cursor pointing
+//! at the current element.
+//!
+//! Moving the cursor and inserting or removing at the cursor are cheap, which
+//! makes the list a good fit for games played around a circle.  Clockwise
+//! (right) is the direction in which elements were inserted with
+//! `insert_after`.
+//!
+//! Removing an element leaves its slot in the vector behind, which is reused
+//! by the next insertion, so the vector never grows beyond the largest number
+//! of elements the list held at once.
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{Index, IndexMut};
+
+pub struct CircularList<T> {
+    nodes: Vec<Node<T>>,
+    cursor: Option<usize>,
+    len: usize,
+    /// Slots of removed elements, to be reused by the next insertions
+    free: Vec<usize>,
+}
+
+#[derive(Clone)]
+struct Node<T> {
+    /// `None` once the element was removed
+    data: Option<T>,
+    /// Index of the node counter-clockwise of this one
+    left: usize,
+    /// Index of the node clockwise of this one
+    right: usize,
+}
+
+impl<T> CircularList<T> {
+    pub fn new() -> CircularList<T> {
+        CircularList::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> CircularList<T> {
+        CircularList {
+            nodes: Vec::with_capacity(capacity),
+            cursor: None,
+            len: 0,
+            free: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of slots in the backing vector, including those of removed
+    /// elements that have not been reused yet.
+    pub fn slots(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The element at the cursor.
+    pub fn current(&self) -> Option<&T> {
+        self.cursor.and_then(|idx| self.nodes[idx].data.as_ref())
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        match self.cursor {
+            Some(idx) => self.nodes[idx].data.as_mut(),
+            None      => None,
+        }
+    }
+
+    /// Add a node between `left` and `right`, preferably in a free slot, and
+    /// return its index.
+    fn link(&mut self, value: T, neighbours: Option<(usize, usize)>) -> usize {
+        let new_idx = self.free.last().cloned().unwrap_or(self.nodes.len());
+        let (left, right) = match neighbours {
+            Some((left, right)) => {
+                self.nodes[left].right = new_idx;
+                self.nodes[right].left = new_idx;
+                (left, right)
+            },
+            None => (new_idx, new_idx),
+        };
+        let node = Node { data: Some(value), left, right };
+        match self.free.pop() {
+            Some(idx) => self.nodes[idx] = node,
+            None      => self.nodes.push(node),
+        }
+        self.len += 1;
+        new_idx
+    }
+
+    /// Insert `value` clockwise of the cursor and move the cursor to it.
+    pub fn insert_after(&mut self, value: T) {
+        let neighbours = self.cursor.map(|idx| (idx, self.nodes[idx].right));
+        self.cursor = Some(self.link(value, neighbours));
+    }
+
+    /// Insert `value` counter-clockwise of the cursor and move the cursor to
+    /// it.
+    pub fn insert_before(&mut self, value: T) {
+        let neighbours = self.cursor.map(|idx| (self.nodes[idx].left, idx));
+        self.cursor = Some(self.link(value, neighbours));
+    }
+
+    /// Remove the element at the cursor and move the cursor to the element
+    /// clockwise of it.
+    pub fn remove(&mut self) -> Option<T> {
+        let cur_idx = self.cursor?;
+        // The node stays in the vector to avoid shifting the others, its
+        // slot is reused by the next insertion
+        let Node { data, left, right } = &mut self.nodes[cur_idx];
+        let (data, left, right) = (data.take(), *left, *right);
+        if right == cur_idx {
+            self.cursor = None;
+        } else {
+            self.nodes[left].right = right;
+            self.nodes[right].left = left;
+            self.cursor = Some(right);
+        }
+        self.len -= 1;
+        self.free.push(cur_idx);
+        data
+    }
+
+    /// Rebuild the backing vector without the slots of removed elements,
+    /// laid out clockwise from the cursor, and release unused memory.
+    pub fn compact(&mut self) {
+        let len = self.len;
+        let mut nodes = Vec::with_capacity(len);
+        let mut next = self.cursor;
+        for idx in 0..len {
+            let node = &mut self.nodes[next.unwrap()];
+            next = Some(node.right);
+            nodes.push(Node {
+                data: node.data.take(),
+                left: (idx + len - 1) % len,
+                right: (idx + 1) % len,
+            });
+        }
+        self.nodes = nodes;
+        self.free = Vec::new();
+        self.cursor = if len == 0 { None } else { Some(0) };
+    }
+
+    /// Move the cursor `steps` elements clockwise, or counter-clockwise if
+    /// `steps` is negative.
+    pub fn rotate(&mut self, steps: isize) {
+        if steps < 0 {
+            self.rotate_left(steps.unsigned_abs());
+        } else {
+            self.rotate_right(steps as usize);
+        }
+    }
+
+    /// Move the cursor `steps` elements clockwise.
+    pub fn rotate_right(&mut self, steps: usize) {
+        if let Some(mut idx) = self.cursor {
+            for _ in 0..steps % self.len {
+                idx = self.nodes[idx].right;
+            }
+            self.cursor = Some(idx);
+        }
+    }
+
+    /// Move the cursor `steps` elements counter-clockwise.
+    pub fn rotate_left(&mut self, steps: usize) {
+        if let Some(mut idx) = self.cursor {
+            for _ in 0..steps % self.len {
+                idx = self.nodes[idx].left;
+            }
+            self.cursor = Some(idx);
+        }
+    }
+
+    /// Iterate clockwise over all elements, starting at the cursor.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { list: self, next: self.cursor, remaining: self.len }
+    }
+
+    /// Index of the node `offset` elements clockwise of the cursor.
+    fn node_index(&self, offset: usize) -> usize {
+        assert!(offset < self.len, "offset {} out of bounds for a list of length {}",
+                offset, self.len);
+        let mut idx = self.cursor.unwrap();
+        for _ in 0..offset {
+            idx = self.nodes[idx].right;
+        }
+        idx
+    }
+}
+
+impl<T> Default for CircularList<T> {
+    fn default() -> CircularList<T> {
+        CircularList::new()
+    }
+}
+
+impl<T: Clone> Clone for CircularList<T> {
+    fn clone(&self) -> CircularList<T> {
+        CircularList {
+            nodes: self.nodes.clone(),
+            cursor: self.cursor,
+            len: self.len,
+            free: self.free.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CircularList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Element `offset` positions clockwise of the cursor, found by walking the
+/// list.
+impl<T> Index<usize> for CircularList<T> {
+    type Output = T;
+
+    fn index(&self, offset: usize) -> &T {
+        self.nodes[self.node_index(offset)].data.as_ref().unwrap()
+    }
+}
+
+impl<T> IndexMut<usize> for CircularList<T> {
+    fn index_mut(&mut self, offset: usize) -> &mut T {
+        let idx = self.node_index(offset);
+        self.nodes[idx].data.as_mut().unwrap()
+    }
+}
+
+/// Builds the list clockwise, with the cursor on the first element.
+impl<T> FromIterator<T> for CircularList<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> CircularList<T> {
+        let iter = iter.into_iter();
+        let mut list = CircularList::with_capacity(iter.size_hint().0);
+        list.extend(iter);
+        list
+    }
+}
+
+/// Inserts the elements counter-clockwise of the cursor, i.e. at the end of
+/// the list, leaving the cursor where it is.
+impl<T> Extend<T> for CircularList<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert_before(value);
+            self.rotate_right(1);
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    list: &'a CircularList<T>,
+    next: Option<usize>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = &self.list.nodes[self.next?];
+        self.next = Some(node.right);
+        self.remaining -= 1;
+        node.data.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> IntoIterator for &'a CircularList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Removes the elements clockwise, starting at the cursor.
+pub struct IntoIter<T> {
+    list: CircularList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.remove()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for CircularList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents<T: Clone>(list: &CircularList<T>) -> Vec<T> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn starts_empty() {
+        let mut list: CircularList<u32> = CircularList::new();
+        assert!(list.is_empty());
+        assert_eq!(None, list.current());
+        assert_eq!(None, list.remove());
+        list.rotate(3);
+        assert_eq!(0, list.iter().count());
+    }
+
+    #[test]
+    fn inserts_after_and_before_cursor() {
+        let mut list = CircularList::new();
+        list.insert_after(1);
+        list.insert_after(2);
+        list.insert_before(3);
+        assert_eq!(Some(&3), list.current());
+        assert_eq!(vec![3, 2, 1], contents(&list));
+        list.insert_after(4);
+        assert_eq!(vec![4, 2, 1, 3], contents(&list));
+        assert_eq!(4, list.len());
+    }
+
+    #[test]
+    fn rotates_in_both_directions() {
+        let mut list: CircularList<u32> = (0..5).collect();
+        list.rotate_right(2);
+        assert_eq!(Some(&2), list.current());
+        list.rotate_left(3);
+        assert_eq!(Some(&4), list.current());
+        list.rotate(-9);
+        assert_eq!(Some(&0), list.current());
+        list.rotate(6);
+        assert_eq!(vec![1, 2, 3, 4, 0], contents(&list));
+    }
+
+    #[test]
+    fn removes_at_cursor() {
+        let mut list: CircularList<u32> = (0..4).collect();
+        list.rotate(2);
+        assert_eq!(Some(2), list.remove());
+        assert_eq!(vec![3, 0, 1], contents(&list));
+        list.rotate(-1);
+        assert_eq!(Some(1), list.remove());
+        assert_eq!(Some(3), list.remove());
+        assert_eq!(Some(0), list.remove());
+        assert!(list.is_empty());
+        assert_eq!(None, list.remove());
+        list.insert_before(7);
+        assert_eq!(vec![7], contents(&list));
+    }
+
+    #[test]
+    fn reuses_removed_slots() {
+        let mut list: CircularList<u32> = (0..4).collect();
+        list.remove();
+        list.remove();
+        list.insert_before(7);
+        list.insert_after(8);
+        list.insert_after(9);
+        assert_eq!(5, list.slots());
+        assert_eq!(vec![9, 2, 3, 7, 8], contents(&list));
+    }
+
+    #[test]
+    fn compacts_slots() {
+        let mut list: CircularList<u32> = (0..6).collect();
+        for _ in 0..3 {
+            list.rotate(1);
+            list.remove();
+        }
+        list.rotate(-1);
+        assert_eq!((6, vec![4, 0, 2]), (list.slots(), contents(&list)));
+        list.compact();
+        assert_eq!((3, vec![4, 0, 2]), (list.slots(), contents(&list)));
+        list.insert_before(6);
+        assert_eq!(vec![6, 4, 0, 2], contents(&list));
+        for _ in 0..4 {
+            list.remove();
+        }
+        list.compact();
+        assert_eq!((0, None), (list.slots(), list.current()));
+    }
+
+    #[test]
+    fn indexes_from_cursor() {
+        let mut list: CircularList<char> = "abcd".chars().collect();
+        list.rotate(1);
+        assert_eq!('b', list[0]);
+        assert_eq!('a', list[3]);
+        list[1] = 'x';
+        assert_eq!(vec!['b', 'x', 'd', 'a'], contents(&list));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn rejects_index_past_end() {
+        let list: CircularList<u32> = (0..3).collect();
+        let _ = list[3];
+    }
+
+    #[test]
+    fn extends_at_end() {
+        let mut list: CircularList<u32> = (0..2).collect();
+        list.rotate(1);
+        list.extend(vec![5, 6]);
+        assert_eq!(Some(&1), list.current());
+        assert_eq!(vec![1, 0, 5, 6], contents(&list));
+    }
+
+    #[test]
+    fn consumes_from_cursor() {
+        let mut list: CircularList<u32> = (0..4).collect();
+        list.rotate(-1);
+        assert_eq!(4, list.iter().len());
+        assert_eq!(vec![&3, &0, &1, &2], (&list).into_iter().collect::<Vec<_>>());
+        assert_eq!(vec![3, 0, 1, 2], list.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn formats_as_list() {
+        let list: CircularList<u32> = (1..4).collect();
+        assert_eq!("[1, 2, 3]", format!("{:?}", list));
+    }
+}
@@ -0,0 +1,38 @@
+use std::collections::HashSet;
+
+use aoc_common::{err, parse_lines, parse_token, Result, Solution};
+
+pub struct Frequencies {
+    changes: Vec<i64>,
+}
+
+impl Solution for Frequencies {
+    type Part1Answer = i64;
+    type Part2Answer = i64;
+
+    fn parse(input: &str) -> Result<Frequencies> {
+        let changes = parse_lines(input, |l| parse_token(l, 0))?;
+        Ok(Frequencies { changes })
+    }
+
+    /// Frequency after the first round of changes
+    fn part1(&self) -> Result<i64> {
+        Ok(self.changes.iter().sum())
+    }
+
+    /// First frequency that is encountered twice
+    fn part2(&self) -> Result<i64> {
+        if self.changes.is_empty() {
+            return err!("No frequency changes passed.");
+        }
+        let mut freq = 0;
+        let mut seen_freqs = HashSet::new();
+        for change in self.changes.iter().cycle() {
+            freq += change;
+            if !seen_freqs.insert(freq) {
+                break;
+            }
+        }
+        Ok(freq)
+    }
+}
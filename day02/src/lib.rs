@@ -1,11 +1,8 @@
 use std::collections::HashMap;
-use std::error;
-use std::io;
-use std::io::BufRead;
 
-type Result<T> = std::result::Result<T, Box<error::Error>>;
+use aoc_common::{err, Result, Solution};
 
-fn checksum_boxes(box_ids: &Vec<String>) -> u64 {
+fn checksum_boxes(box_ids: &[String]) -> u64 {
     let mut two_ids = 0;
     let mut three_ids = 0;
     for box_id in box_ids {
@@ -22,16 +19,16 @@ fn checksum_boxes(box_ids: &Vec<String>) -> u64 {
             three_ids += 1;
         }
     }
-    return two_ids * three_ids;
+    two_ids * three_ids
 }
 
 fn string_dist(a: &str, b: &str) -> u64 {
     let mut dist = a.chars().zip(b.chars()).map(|(x, y)| (x != y) as u64).sum();
-    dist += (a.len() as i64 - b.len() as i64).abs() as u64;
-    return dist;
+    dist += (a.len() as i64 - b.len() as i64).unsigned_abs();
+    dist
 }
 
-fn find_right_boxes(box_ids: &Vec<String>) -> Option<(String, String)> {
+fn find_right_boxes(box_ids: &[String]) -> Option<(String, String)> {
     for a in box_ids {
         for b in box_ids {
             if string_dist(a, b) == 1 {
@@ -46,16 +43,28 @@ fn get_common_chars(a: &str, b: &str) -> String {
     a.chars().zip(b.chars()).filter(|(x, y)| x == y).map(|(x, _)| x).collect()
 }
 
-fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let box_ids: Vec<String> = stdin.lock().lines()
-        .collect::<std::result::Result<_, _>>()?;
-    let checksum = checksum_boxes(&box_ids);
-    println!("List checksum: {}", checksum);
-    match find_right_boxes(&box_ids) {
-        Some((a, b)) => println!("Common characters: {}",
-                                 get_common_chars(&a, &b)),
-        None => println!("No boxes found!")
-    };
-    Ok(())
+pub struct BoxIds {
+    ids: Vec<String>,
+}
+
+impl Solution for BoxIds {
+    type Part1Answer = u64;
+    type Part2Answer = String;
+
+    fn parse(input: &str) -> Result<BoxIds> {
+        Ok(BoxIds { ids: input.lines().map(|l| l.to_string()).collect() })
+    }
+
+    /// Checksum of the box list
+    fn part1(&self) -> Result<u64> {
+        Ok(checksum_boxes(&self.ids))
+    }
+
+    /// Common characters of the two boxes that differ by one character
+    fn part2(&self) -> Result<String> {
+        match find_right_boxes(&self.ids) {
+            Some((a, b)) => Ok(get_common_chars(&a, &b)),
+            None => err!("No boxes found!")
+        }
+    }
 }
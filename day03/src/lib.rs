@@ -0,0 +1,156 @@
+#[macro_use] extern crate lazy_static;
+extern crate regex;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use aoc_common::{err, parse_lines, parse_token, Error, Result, Solution};
+use regex::Regex;
+
+
+pub struct PositionIter<'a> {
+    claim: &'a Claim,
+    next_pos: Option<(u32, u32)>
+}
+
+impl<'a> Iterator for PositionIter<'a> {
+    type Item = (u32, u32);
+
+    fn next(&mut self) -> Option<(u32, u32)> {
+        let (x, y) = self.next_pos?;
+        self.next_pos = if x < self.claim.lrx {
+            // Increment column
+            Some((x + 1, y))
+        } else if y < self.claim.lry {
+            // Increment row
+            Some((self.claim.ulx, y + 1))
+        } else {
+            None
+        };
+        Some((x, y))
+    }
+}
+
+#[derive(Debug, Hash, PartialEq)]
+pub struct Claim {
+    id: u32,
+    ulx: u32,
+    uly: u32,
+    lrx: u32,
+    lry: u32
+}
+
+impl FromStr for Claim {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Claim> {
+        lazy_static! {
+            // #<id> @ <x>,<y>: <w>x<h>, with anything but the separators
+            // accepted as a number so `parse_token` can point at a bad one
+            static ref CLAIM_RE: Regex = Regex::new(
+                r"^#(?P<id>\S+) @ (?P<x>[^,\s]+),(?P<y>[^:\s]+): (?P<w>[^x\s]+)x(?P<h>\S+)$").unwrap();
+        }
+        let caps = CLAIM_RE.captures(s)
+            .ok_or_else(|| Error::parse(0, s, "expected `#<id> @ <x>,<y>: <w>x<h>`"))?;
+        let field = |name| {
+            let m = caps.name(name).unwrap();
+            parse_token::<u32>(m.as_str(), m.start())
+        };
+        let x = field("x")?;
+        let y = field("y")?;
+        let w = field("w")?;
+        let h = field("h")?;
+        if w == 0 || h == 0 {
+            let m = caps.name(if w == 0 { "w" } else { "h" }).unwrap();
+            return Err(Error::parse(m.start(), m.as_str(), "claims must not be empty"));
+        }
+
+        let far_edge = |start: u32, size: u32, name| {
+            start.checked_add(size - 1).ok_or_else(|| {
+                let m = caps.name(name).unwrap();
+                Error::parse(m.start(), m.as_str(), "claim extends past the largest coordinate")
+            })
+        };
+
+        Ok(Claim {
+            id: field("id")?,
+            ulx: x,
+            uly: y,
+            lrx: far_edge(x, w, "w")?,
+            lry: far_edge(y, h, "h")?,
+        })
+    }
+}
+
+impl Claim {
+    pub fn positions(&self) -> PositionIter<'_> {
+        PositionIter {
+            claim: self,
+            next_pos: Some((self.ulx, self.uly))
+        }
+    }
+}
+
+
+fn count_positions(claims: &[Claim]) -> HashMap<(u32, u32), u32> {
+    let mut positions: HashMap<(u32, u32), u32> = HashMap::new();
+    for claim in claims {
+        for pos in claim.positions() {
+            *positions.entry(pos).or_insert(0) += 1;
+        }
+    }
+    positions
+}
+
+pub struct Claims {
+    claims: Vec<Claim>,
+}
+
+impl Solution for Claims {
+    type Part1Answer = usize;
+    type Part2Answer = u32;
+
+    fn parse(input: &str) -> Result<Claims> {
+        let claims = parse_lines(input, str::parse)?;
+        Ok(Claims { claims })
+    }
+
+    /// Number of positions claimed at least twice
+    fn part1(&self) -> Result<usize> {
+        let positions = count_positions(&self.claims);
+        Ok(positions.values().filter(|&&v| v >= 2).count())
+    }
+
+    /// Id of the only claim that does not overlap any other claim
+    fn part2(&self) -> Result<u32> {
+        let positions = count_positions(&self.claims);
+        let non_overlap = self.claims.iter()
+            .find(|&c| !c.positions().any(|pos| positions[&pos] > 1));
+        match non_overlap {
+            Some(claim) => Ok(claim.id),
+            None => err!("No claims without overlaps found!")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::test_support::parse_error;
+
+    use super::*;
+
+    #[test]
+    fn rejects_claims_past_the_largest_coordinate() {
+        assert_eq!((20, "claim extends past the largest coordinate".to_string()),
+                   parse_error::<Claim>("#1 @ 4294967295,0: 2x1"));
+        assert!("#1 @ 4294967295,0: 1x1".parse::<Claim>().is_ok());
+    }
+
+    #[test]
+    fn covers_claims_at_the_edges() {
+        let claim: Claim = "#1 @ 0,0: 2x2".parse().unwrap();
+        assert_eq!(vec![(0, 0), (1, 0), (0, 1), (1, 1)], claim.positions().collect::<Vec<_>>());
+        let claim: Claim = "#2 @ 4294967294,7: 2x1".parse().unwrap();
+        assert_eq!(vec![(4294967294, 7), (4294967295, 7)], claim.positions().collect::<Vec<_>>());
+    }
+}
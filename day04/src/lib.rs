@@ -0,0 +1,160 @@
+#[macro_use] extern crate lazy_static;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use aoc_common::{err, parse_lines, parse_token, tokens, Error, Result, Solution};
+use regex::Regex;
+
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord)]
+struct Timestamp {
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32
+}
+
+#[derive(Debug)]
+enum GuardEvent {
+    BeginShift(u32),
+    Wake,
+    Sleep
+}
+
+impl FromStr for GuardEvent {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<GuardEvent> {
+        let parts: Vec<(usize, &str)> = tokens(s).collect();
+        match parts.as_slice() {
+            [(_, "Guard"), (start, id), ..] if id.starts_with('#') =>
+                Ok(GuardEvent::BeginShift(parse_token(&id[1..], start + 1)?)),
+            [(_, "wakes"), ..] => Ok(GuardEvent::Wake),
+            [(_, "falls"), ..] => Ok(GuardEvent::Sleep),
+            _ => Err(Error::parse(0, s, "unknown event"))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GuardLogEntry {
+    event: GuardEvent,
+    time: Timestamp,
+}
+
+impl FromStr for GuardLogEntry {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<GuardLogEntry> {
+        lazy_static! {
+            // Only the punctuation of the timestamp is checked here, its
+            // numbers and the event are validated once split up
+            static ref LOG_RE: Regex = Regex::new(
+                r"^\[(?P<y>[^-\s]+)-(?P<m>[^-\s]+)-(?P<d>\S+) (?P<h>[^:\s]+):(?P<M>[^\]\s]+)\] (?P<evt>.+)$").unwrap();
+        }
+        let caps = LOG_RE.captures(s)
+            .ok_or_else(|| Error::parse(0, s, "expected `[YYYY-MM-DD hh:mm] <event>`"))?;
+        let field = |name| {
+            let m = caps.name(name).unwrap();
+            parse_token::<u32>(m.as_str(), m.start())
+        };
+        let evt = caps.name("evt").unwrap();
+        let minute = caps.name("M").unwrap();
+        if field("M")? >= 60 {
+            return Err(Error::parse(minute.start(), minute.as_str(),
+                                    "minute must be less than 60"));
+        }
+        Ok(GuardLogEntry {
+            time: Timestamp {
+                year: field("y")?, month: field("m")?,
+                day: field("d")?, hour: field("h")?,
+                minute: field("M")? },
+            event: evt.as_str().parse().map_err(|e: Error| e.offset(evt.start()))? })
+    }
+}
+
+#[derive(Debug)]
+pub struct GuardLog {
+    entries: Vec<GuardLogEntry>,
+}
+
+impl GuardLog {
+    pub fn new(mut entries: Vec<GuardLogEntry>) -> Result<GuardLog> {
+        entries.sort_unstable_by(|a, b| a.time.cmp(&b.time));
+        if entries.is_empty() {
+            return err!("There must be at least one log entry!");
+        }
+        match entries[0].event {
+            GuardEvent::BeginShift(_) => Ok(GuardLog { entries }),
+            _ => err!("First log entry must be begin of a shift!")
+        }
+    }
+
+    fn get_sleep_times(&self) -> HashMap<u32, [u32; 60]> {
+        let mut guard_times: HashMap<u32, [u32; 60]> = HashMap::new();
+        let mut sleep_start: u32 = 0;
+        let mut current_times: Option<&mut [u32; 60]> = None;
+        for entry in &self.entries {
+            match entry.event {
+                GuardEvent::BeginShift(guard_id) => {
+                    current_times = Some(guard_times.entry(guard_id)
+                        .or_insert([0; 60]));
+                },
+                GuardEvent::Sleep => sleep_start = entry.time.minute,
+                GuardEvent::Wake  => {
+                    if let Some(ts) = current_times.as_mut() {
+                        for minute in sleep_start..entry.time.minute {
+                            ts[minute as usize] += 1;
+                        }
+                    }
+                }
+            }
+        }
+        guard_times
+    }
+
+    pub fn strategy_one(&self) -> (u32, u32) {
+        let sleep_times = self.get_sleep_times();
+        let (guard_id, ts) = sleep_times.iter()
+            .max_by(|(_, a), (_, b)| {
+                a.iter().sum::<u32>().cmp(&b.iter().sum::<u32>())
+            }).unwrap();
+        let sleepy_minute = ts.iter().enumerate()
+            .max_by_key(|&(_, v)| v).unwrap().0 as u32;
+        (*guard_id, sleepy_minute)
+    }
+
+    pub fn strategy_two(&self) -> (u32, u32) {
+        let mut sleepiest_minutes: HashMap<u32, (u32, u32)> = HashMap::new();
+        for (guard, times) in self.get_sleep_times() {
+            let (min, cnt) = times.iter().enumerate()
+                .max_by_key(|&(_, v)| v).unwrap();
+            sleepiest_minutes.insert(guard, (min as u32, *cnt));
+        }
+        let (&long_guard, &(min, _)) = sleepiest_minutes.iter()
+            .max_by_key(|(_, (_, cnt))| cnt).unwrap();
+        (long_guard, min)
+    }
+}
+
+impl Solution for GuardLog {
+    type Part1Answer = u32;
+    type Part2Answer = u32;
+
+    fn parse(input: &str) -> Result<GuardLog> {
+        GuardLog::new(parse_lines(input, str::parse)?)
+    }
+
+    /// Checksum of the guard that sleeps the most and their sleepiest minute
+    fn part1(&self) -> Result<u32> {
+        let (guard_id, minute) = self.strategy_one();
+        Ok(guard_id * minute)
+    }
+
+    /// Checksum of the guard that is most often asleep on the same minute
+    fn part2(&self) -> Result<u32> {
+        let (guard_id, minute) = self.strategy_two();
+        Ok(guard_id * minute)
+    }
+}
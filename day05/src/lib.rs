@@ -0,0 +1,54 @@
+use aoc_common::{Result, Solution};
+
+fn toggle_case(c: char) -> char {
+    if c.is_lowercase() {
+        c.to_ascii_uppercase()
+    } else {
+        c.to_ascii_lowercase()
+    }
+}
+
+fn reduce(chain: &str) -> String {
+    chain.chars().fold(String::new(), |mut r, c| {
+        if r.ends_with(toggle_case(c)) {
+            r.pop();
+        } else {
+            r.push(c);
+        }
+        r
+    })
+}
+
+fn find_maximum_reduction(chain: &str) -> String {
+    "abcdefghijklmnopqrstuvwxyz".chars()
+        .map(|c| -> String {
+            chain.chars()
+                .filter(|&x| x != c && x != toggle_case(c))
+                .collect() })
+        .map(|c| reduce(&c))
+        .min_by_key(|c| c.len())
+        .unwrap()
+}
+
+pub struct Polymer {
+    chain: String,
+}
+
+impl Solution for Polymer {
+    type Part1Answer = usize;
+    type Part2Answer = usize;
+
+    fn parse(input: &str) -> Result<Polymer> {
+        Ok(Polymer { chain: input.trim().to_owned() })
+    }
+
+    /// Length of the fully reduced chain
+    fn part1(&self) -> Result<usize> {
+        Ok(reduce(&self.chain).len())
+    }
+
+    /// Length of the shortest chain after removing a single unit type
+    fn part2(&self) -> Result<usize> {
+        Ok(find_maximum_reduction(&self.chain).len())
+    }
+}
@@ -1,11 +1,10 @@
 use std::cmp;
-use std::error::Error;
-use std::io::{self, Read};
 use std::collections::{HashMap, HashSet};
 
+use aoc_common::{err, parse_lines, parse_token, tokens, Error, Result, Solution};
 
-type Result<T> = std::result::Result<T, Box<Error>>;
-type Point = (u32, u32);
+
+pub type Point = (u32, u32);
 
 
 struct Grid {
@@ -49,12 +48,12 @@ impl Grid {
 
 
 fn dist((ax, ay): &Point, (bx, by): &Point) -> u32 {
-    (cmp::max(ax, bx) - cmp::min(ax, bx) +
-     cmp::max(ay, by) - cmp::min(ay, by))
+    cmp::max(ax, bx) - cmp::min(ax, bx) +
+    cmp::max(ay, by) - cmp::min(ay, by)
 }
 
 
-fn part1(center_points: &[(u32, u32)]) {
+fn part1(center_points: &[Point]) -> Result<usize> {
     let grid = Grid::new(center_points);
     let mut point_owners: HashMap<Point, char> = HashMap::new();
     let mut owned_points: HashMap<char, HashSet<Point>> = HashMap::new();
@@ -78,36 +77,56 @@ fn part1(center_points: &[(u32, u32)]) {
             }
         }
         if owner != '.' && !point_owners.contains_key(&pt) {
-            owned_points.entry(owner).or_insert(HashSet::new()).insert(pt);
+            owned_points.entry(owner).or_default().insert(pt);
         }
         point_owners.insert(pt, owner);
     }
-    let largest_area = owned_points.values()
-        .filter(|&pts| grid.is_finite(pts))
-        .map(|pts| pts.len())
-        .max().unwrap();
-    println!("Largest area: {}", largest_area);
+    match owned_points.values().filter(|&pts| grid.is_finite(pts)).map(|pts| pts.len()).max() {
+        Some(size) => Ok(size),
+        None       => err!("No finite area found"),
+    }
 }
 
-fn part2(center_points: &[(u32, u32)]) {
+fn part2(center_points: &[Point]) -> usize {
     let grid = Grid::new(center_points);
-    let size = grid.iter_points()
+    grid.iter_points()
         .filter(|pt| grid.dist_to_centers(pt) < 10000)
-        .count();
-    println!("Size of region: {}", size);
+        .count()
+}
+
+
+fn parse_point(line: &str) -> Result<Point> {
+    let coords: Vec<(usize, &str)> = tokens(line).collect();
+    match coords.as_slice() {
+        [(x_start, x), (y_start, y)] if x.ends_with(',') =>
+            Ok((parse_token(&x[..x.len() - 1], *x_start)?, parse_token(y, *y_start)?)),
+        _ => Err(Error::parse(0, line, "expected `<x>, <y>`")),
+    }
+}
+
+pub struct Coordinates {
+    points: Vec<Point>,
 }
 
+impl Solution for Coordinates {
+    type Part1Answer = usize;
+    type Part2Answer = usize;
+
+    fn parse(input: &str) -> Result<Coordinates> {
+        let points = parse_lines(input, parse_point)?;
+        if points.is_empty() {
+            return err!("There must be at least one point!");
+        }
+        Ok(Coordinates { points })
+    }
 
-fn main() -> Result<()> {
-    let mut buf = String::new();
-    io::stdin().read_to_string(&mut buf)?;
-    let points: Vec<(u32, u32)> = buf.split("\n")
-        .filter(|l| l.trim().len() > 0)
-        .map(|l| l.trim().split(", ").collect::<Vec<&str>>())
-        .map(|s| (s[0], s[1]))
-        .map(|(x, y)| Ok((x.parse::<u32>()?, y.parse::<u32>()?)))
-        .collect::<Result<_>>()?;
-    part1(&points);
-    part2(&points);
-    Ok(())
+    /// Size of the largest finite area
+    fn part1(&self) -> Result<usize> {
+        part1(&self.points)
+    }
+
+    /// Size of the region with a total distance of less than 10000
+    fn part2(&self) -> Result<usize> {
+        Ok(part2(&self.points))
+    }
 }
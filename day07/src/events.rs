@@ -0,0 +1,159 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+use aoc_common::{err, parse_lines, Error, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::scheduler::Scheduler;
+use crate::worker::Worker;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    /// The worker began working on the step
+    Start,
+    /// The worker completed the step
+    Finish,
+    /// The worker has no step to work on
+    Idle,
+}
+
+/// Something that happened to a worker during the simulation, serialized as
+/// `{"tick":0,"worker":1,"event":"start","step":"C"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event<Id> {
+    pub tick: u64,
+    pub worker: usize,
+    pub event: EventKind,
+    /// The step that was started or finished, `None` for idle events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<Id>,
+}
+
+/// One JSON object per line for every event.
+pub fn to_json_lines<Id: Serialize>(events: &[Event<Id>]) -> String {
+    events.iter()
+        .map(|event| serde_json::to_string(event).unwrap() + "\n")
+        .collect()
+}
+
+/// Read a log written by `to_json_lines`, ignoring blank lines.
+pub fn from_json_lines<Id: DeserializeOwned>(input: &str) -> Result<Vec<Event<Id>>> {
+    parse_lines(input, |line| {
+        serde_json::from_str(line).map_err(|e| {
+            let start = e.column().saturating_sub(1).min(line.len());
+            let start = (0..=start).rev().find(|&idx| line.is_char_boundary(idx)).unwrap_or(0);
+            // The position is already part of the diagnostic
+            let reason = e.to_string();
+            let position = format!(" at line {} column {}", e.line(), e.column());
+            Error::parse(start, "", reason.trim_end_matches(&position))
+        })
+    })
+}
+
+impl<Id: Ord + Clone + Display> Scheduler<Id> {
+    /// Check that a log of events could have come from `workers` working on
+    /// the steps: events are ordered by tick, every worker works on at most
+    /// one step it has the skills for, steps are only started once their
+    /// dependencies are finished and all steps are finished in the end.
+    ///
+    /// Finish events must precede the start events of the same tick that
+    /// depend on them.
+    pub fn replay(&self, events: &[Event<Id>], workers: &[Worker]) -> Result<()> {
+        let mut busy: Vec<Option<&Id>> = vec![None; workers.len()];
+        let mut started: BTreeSet<&Id> = BTreeSet::new();
+        let mut finished: BTreeSet<&Id> = BTreeSet::new();
+        let mut predecessors: BTreeMap<&Id, Vec<&Id>> = BTreeMap::new();
+        for (before, after) in self.dependencies() {
+            predecessors.entry(after).or_default().push(before);
+        }
+        let mut tick = 0;
+        for (idx, event) in events.iter().enumerate() {
+            let fail = |reason: String| err!("Event {} (tick {}, worker {}): {}",
+                                             idx + 1, event.tick, event.worker, reason);
+            if event.tick < tick {
+                return fail(format!("goes back in time from tick {}", tick));
+            }
+            tick = event.tick;
+            if event.worker >= workers.len() {
+                return fail(format!("there are only {} workers", workers.len()));
+            }
+            let step = match (event.event, &event.step) {
+                (EventKind::Idle, None) => {
+                    if let Some(step) = busy[event.worker] {
+                        return fail(format!("idle while working on step {}", step));
+                    }
+                    continue;
+                },
+                (EventKind::Idle, Some(step)) => return fail(format!("idle event names step {}", step)),
+                (_, None) => return fail("no step given".to_string()),
+                (_, Some(step)) => match self.step(step) {
+                    Some(step) => step,
+                    None       => return fail(format!("unknown step {}", step)),
+                },
+            };
+            if event.event == EventKind::Finish {
+                if busy[event.worker] != Some(step) {
+                    return fail(format!("finishes step {} without working on it", step));
+                }
+                busy[event.worker] = None;
+                finished.insert(step);
+                continue;
+            }
+            if let Some(other) = busy[event.worker] {
+                return fail(format!("starts step {} while working on step {}", step, other));
+            }
+            if started.contains(step) {
+                return fail(format!("step {} was already started", step));
+            }
+            let no_skills = Default::default();
+            if !workers[event.worker].can_do(self.required_skills(step).unwrap_or(&no_skills)) {
+                return fail(format!("lacks the skills for step {}", step));
+            }
+            if let Some(before) = predecessors.get(step).into_iter().flatten()
+                    .find(|before| !finished.contains(*before)) {
+                return fail(format!("starts step {} before step {} is finished", step, before));
+            }
+            busy[event.worker] = Some(step);
+            started.insert(step);
+        }
+        if let Some((worker, step)) = busy.iter().enumerate()
+                .find_map(|(worker, step)| step.map(|step| (worker, step))) {
+            return err!("Worker {} never finishes step {}", worker, step);
+        }
+        if let Some(step) = self.steps().find(|step| !finished.contains(step)) {
+            return err!("Step {} is never finished", step);
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_simulated_log() {
+        let mut scheduler = Scheduler::new();
+        scheduler.add_dependency("A".to_string(), "B".to_string());
+        scheduler.add_step("C".to_string());
+        let workers = vec![Worker::new(); 2];
+        let schedule = scheduler.run(2, &|_: &String| 2).unwrap();
+        let log = to_json_lines(&schedule.events);
+        assert!(log.starts_with("{\"tick\":0,\"worker\":0,\"event\":\"start\",\"step\":\"A\"}\n"));
+        let mut events: Vec<Event<String>> = from_json_lines(&log).unwrap();
+        assert_eq!(schedule.events, events);
+        assert!(scheduler.replay(&events, &workers).is_ok());
+
+        // Let the other worker take B while A is still being worked on
+        events.retain(|event| event.step.as_deref() != Some("A") || event.event != EventKind::Finish);
+        for event in events.iter_mut().filter(|event| event.step.as_deref() == Some("B")) {
+            event.worker = 1;
+        }
+        assert!(scheduler.replay(&events, &workers).unwrap_err().to_string()
+                .ends_with("starts step B before step A is finished"));
+        assert!(scheduler.replay(&schedule.events, &workers[..1]).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+
+use crate::schedule::Schedule;
+use crate::scheduler::Scheduler;
+
+/// Colours for the workers, cycled through if there are more workers
+const WORKER_COLOURS: [&str; 8] = [
+    "#66c2a5", "#fc8d62", "#8da0cb", "#e78ac3",
+    "#a6d854", "#ffd92f", "#e5c494", "#b3b3b3",
+];
+/// Colours for the first and the last step in the completion order
+const ORDER_GRADIENT: [(u8, u8, u8); 2] = [(255, 245, 235), (217, 72, 1)];
+
+/// How to colour the steps of an exported graph.
+pub enum Highlight<'a, Id> {
+    None,
+    /// Shade the steps by the position in which they are completed
+    Order(&'a [Id]),
+    /// Give every worker its own colour and colour the steps by the worker
+    /// that completed them
+    Worker(&'a Schedule<Id>),
+}
+
+/// Fill colour and extra label line for every highlighted step.
+fn styles<'a, Id: Ord>(highlight: &'a Highlight<Id>) -> BTreeMap<&'a Id, (String, String)> {
+    match highlight {
+        Highlight::None => BTreeMap::new(),
+        Highlight::Order(order) => {
+            let last = order.len().saturating_sub(1).max(1) as f64;
+            order.iter().enumerate()
+                .map(|(pos, step)| {
+                    let ratio = pos as f64 / last;
+                    let [from, to] = ORDER_GRADIENT;
+                    let channel = |a: u8, b: u8| {
+                        (f64::from(a) + (f64::from(b) - f64::from(a)) * ratio).round() as u8
+                    };
+                    let colour = format!("#{:02x}{:02x}{:02x}", channel(from.0, to.0),
+                                         channel(from.1, to.1), channel(from.2, to.2));
+                    (step, (colour, format!("#{}", pos + 1)))
+                })
+                .collect()
+        },
+        Highlight::Worker(schedule) => {
+            schedule.workers.iter().enumerate()
+                .flat_map(|(worker, intervals)| {
+                    intervals.iter().map(move |interval| {
+                        let colour = WORKER_COLOURS[worker % WORKER_COLOURS.len()];
+                        (&interval.step, (colour.to_string(), format!("worker {}", worker)))
+                    })
+                })
+                .collect()
+        },
+    }
+}
+
+fn dot_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+impl<Id: Ord + Clone + Display> Scheduler<Id> {
+    /// Render the dependencies as a Graphviz DOT digraph.
+    pub fn to_dot(&self, highlight: &Highlight<Id>) -> String {
+        let styles = styles(highlight);
+        let mut dot = String::from("digraph steps {\n    rankdir=LR;\n    node [shape=box];\n");
+        for step in self.steps() {
+            let id = dot_escape(&step.to_string());
+            match styles.get(step) {
+                Some((colour, note)) => writeln!(
+                    dot, "    \"{}\" [label=\"{}\\n{}\", style=filled, fillcolor=\"{}\"];",
+                    id, id, dot_escape(note), colour).unwrap(),
+                None => writeln!(dot, "    \"{}\";", id).unwrap(),
+            }
+        }
+        for (before, after) in self.dependencies() {
+            writeln!(dot, "    \"{}\" -> \"{}\";",
+                     dot_escape(&before.to_string()), dot_escape(&after.to_string())).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render the dependencies as a Mermaid flowchart.
+    ///
+    /// Steps are given generated node ids, since Mermaid only accepts
+    /// alphanumeric ones.
+    pub fn to_mermaid(&self, highlight: &Highlight<Id>) -> String {
+        let styles = styles(highlight);
+        let node_ids: BTreeMap<&Id, String> = self.steps().enumerate()
+            .map(|(idx, step)| (step, format!("s{}", idx)))
+            .collect();
+        let mut mermaid = String::from("graph LR\n");
+        for (step, node) in &node_ids {
+            let label = mermaid_escape(&step.to_string());
+            match styles.get(step) {
+                Some((_, note)) => writeln!(mermaid, "    {}[\"{}<br/>{}\"]",
+                                            node, label, mermaid_escape(note)).unwrap(),
+                None => writeln!(mermaid, "    {}[\"{}\"]", node, label).unwrap(),
+            }
+        }
+        for (before, after) in self.dependencies() {
+            writeln!(mermaid, "    {} --> {}", node_ids[before], node_ids[after]).unwrap();
+        }
+        for (step, (colour, _)) in &styles {
+            if let Some(node) = node_ids.get(step) {
+                writeln!(mermaid, "    style {} fill:{}", node, colour).unwrap();
+            }
+        }
+        mermaid
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colours_steps_by_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.add_dependency('A', 'B');
+        let order = scheduler.order().unwrap();
+        let mermaid = scheduler.to_mermaid(&Highlight::Order(&order));
+        assert_eq!("graph LR\n    s0[\"A<br/>#1\"]\n    s1[\"B<br/>#2\"]\n    s0 --> s1\n\
+                    \x20   style s0 fill:#fff5eb\n    style s1 fill:#d94801\n", mermaid);
+        assert!(scheduler.to_dot(&Highlight::None).contains("    \"A\" -> \"B\";\n"));
+    }
+}
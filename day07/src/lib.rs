@@ -0,0 +1,161 @@
+use aoc_common::{parse_lines, Result, Solution};
+
+mod events;
+mod graph;
+mod rule;
+mod schedule;
+mod scheduler;
+mod worker;
+
+pub use crate::events::{from_json_lines, to_json_lines, Event, EventKind};
+pub use crate::graph::Highlight;
+pub use crate::rule::Rule;
+pub use crate::schedule::{Interval, Schedule};
+pub use crate::scheduler::{CostModel, LetterCost, Scheduler};
+pub use crate::worker::{FastestFirst, LowestId, RoundRobin, Worker, WorkerPolicy};
+
+/// Number of workers available for part 2 of the puzzle
+pub const NUM_WORKERS: usize = 5;
+/// Seconds every step takes in addition to its letter cost in part 2
+pub const BASE_DURATION: u32 = 60;
+
+pub struct Instructions {
+    scheduler: Scheduler<String>,
+}
+
+impl Instructions {
+    pub fn scheduler(&self) -> &Scheduler<String> {
+        &self.scheduler
+    }
+
+    pub fn scheduler_mut(&mut self) -> &mut Scheduler<String> {
+        &mut self.scheduler
+    }
+
+    /// Schedule for `num_workers` workers, with every step taking
+    /// `base_duration` seconds in addition to its letter cost
+    pub fn schedule(&self, num_workers: usize, base_duration: u32) -> Result<Schedule<String>> {
+        self.scheduler.run(num_workers, &LetterCost { base: base_duration })
+    }
+
+    /// Schedule for the given `workers`, assigned to the steps by `policy`
+    pub fn schedule_with(&self, workers: &[Worker], base_duration: u32,
+                         policy: &mut dyn WorkerPolicy) -> Result<Schedule<String>> {
+        self.scheduler.run_with(workers, &LetterCost { base: base_duration }, policy)
+    }
+}
+
+impl Solution for Instructions {
+    type Part1Answer = String;
+    type Part2Answer = Schedule<String>;
+
+    fn parse(input: &str) -> Result<Instructions> {
+        let mut scheduler = Scheduler::new();
+        for Rule { before, after } in parse_lines(input, str::parse)? {
+            scheduler.add_dependency(before, after);
+        }
+        Ok(Instructions { scheduler })
+    }
+
+    /// Order in which the steps are completed by a single worker
+    fn part1(&self) -> Result<String> {
+        Ok(self.scheduler.order()?.concat())
+    }
+
+    /// Schedule for five workers to complete all steps
+    fn part2(&self) -> Result<Schedule<String>> {
+        self.schedule(NUM_WORKERS, BASE_DURATION)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "\
+Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.
+";
+
+    #[test]
+    fn solves_example() {
+        let instructions = Instructions::parse(EXAMPLE).unwrap();
+        assert_eq!("CABDFE", instructions.part1().unwrap());
+        let schedule = instructions.schedule(2, 0).unwrap();
+        assert_eq!(15, schedule.duration);
+        assert_eq!((vec!["C", "F", "E"], 14),
+                   (schedule.critical_path.iter().map(String::as_str).collect(),
+                    schedule.critical_duration));
+        let steps: Vec<Vec<&str>> = schedule.workers.iter()
+            .map(|intervals| intervals.iter().map(|i| i.step.as_str()).collect())
+            .collect();
+        assert_eq!(vec![vec!["C", "A", "B", "D", "E"], vec!["F"]], steps);
+    }
+
+    /// Random acyclic dependencies between up to 12 steps named by letters,
+    /// with the letters shuffled so the alphabetical order is not already a
+    /// valid one.
+    fn acyclic_scheduler() -> impl Strategy<Value=Scheduler<char>> {
+        (1usize..=12).prop_flat_map(|num_steps| {
+            let letters: Vec<char> = (b'A'..).take(num_steps).map(char::from).collect();
+            (Just(letters).prop_shuffle(),
+             prop::collection::vec((0..num_steps, 0..num_steps), 0..=num_steps * 2))
+        }).prop_map(|(letters, edges)| {
+            let mut scheduler = Scheduler::new();
+            for &step in &letters {
+                scheduler.add_step(step);
+            }
+            for (a, b) in edges.into_iter().filter(|(a, b)| a != b) {
+                scheduler.add_dependency(letters[a.min(b)], letters[a.max(b)]);
+            }
+            scheduler
+        })
+    }
+
+    fn letter_cost(base: u32) -> impl Fn(&char) -> u32 {
+        move |step: &char| u32::from(*step as u8 - b'A') + 1 + base
+    }
+
+    proptest! {
+        #[test]
+        fn order_is_topological(scheduler in acyclic_scheduler()) {
+            let order = scheduler.order().unwrap();
+            let position = |step: &char| order.iter().position(|s| s == step).unwrap();
+            prop_assert_eq!(scheduler.steps().count(), order.len());
+            for (before, after) in scheduler.dependencies() {
+                prop_assert!(position(before) < position(after));
+            }
+        }
+
+        #[test]
+        fn schedule_respects_dependencies(scheduler in acyclic_scheduler(),
+                                          num_workers in 1usize..=6, base in 0u32..=5) {
+            let cost = letter_cost(base);
+            let serial: u64 = scheduler.steps().map(|step| u64::from(cost(step))).sum();
+            let schedule = scheduler.run(num_workers, &cost).unwrap();
+            prop_assert_eq!(serial, scheduler.run(1, &cost).unwrap().duration);
+            prop_assert!(schedule.duration <= serial);
+            prop_assert!(schedule.duration >= schedule.critical_duration);
+
+            let intervals: Vec<&Interval<char>> = schedule.workers.iter().flatten().collect();
+            let interval = |step: &char| intervals.iter().find(|i| i.step == *step).unwrap();
+            prop_assert_eq!(scheduler.steps().count(), intervals.len());
+            for (before, after) in scheduler.dependencies() {
+                prop_assert!(interval(before).end <= interval(after).start);
+            }
+            for worker in &schedule.workers {
+                for pair in worker.windows(2) {
+                    prop_assert!(pair[0].end <= pair[1].start);
+                }
+            }
+            prop_assert!(scheduler.replay(&schedule.events, &vec![Worker::new(); num_workers]).is_ok());
+        }
+    }
+}
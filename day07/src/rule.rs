@@ -0,0 +1,61 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::{match_tokens, Error, Result};
+
+/// Tokens of an instruction, `None` marks the positions of the step ids.
+const GRAMMAR: [Option<&str>; 10] = [
+    Some("Step"), None, Some("must"), Some("be"), Some("finished"),
+    Some("before"), Some("step"), None, Some("can"), Some("begin."),
+];
+
+/// Instruction that step `before` must be finished before step `after` can
+/// begin.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rule {
+    pub before: String,
+    pub after: String,
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Rule> {
+        let mut steps = match_tokens(s, &GRAMMAR, "a step id", "instruction")?.into_iter()
+            .map(|(_, step)| step.to_string());
+        let before = steps.next().unwrap();
+        let after = steps.next().unwrap();
+        Ok(Rule { before, after })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Step {} must be finished before step {} can begin.", self.before, self.after)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::test_support::parse_error;
+
+    use super::*;
+
+    #[test]
+    fn parses_multi_character_ids() {
+        let rule: Rule = "Step compile must be finished before step link can begin."
+            .parse().unwrap();
+        assert_eq!(Rule { before: "compile".into(), after: "link".into() }, rule);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert_eq!((8, "expected `must`".to_string()),
+                   parse_error::<Rule>("Step C should be finished before step A can begin."));
+        assert_eq!((38, "expected `can`".to_string()),
+                   parse_error::<Rule>("Step C must be finished before step A"));
+        assert_eq!((50, "unexpected text after instruction".to_string()),
+                   parse_error::<Rule>("Step C must be finished before step A can begin. Now!"));
+    }
+}
@@ -0,0 +1,101 @@
+use std::fmt::{self, Display, Write};
+
+use crate::events::Event;
+
+/// Time span during which a worker was busy with a step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interval<Id> {
+    pub step: Id,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Result of simulating the workers, displayed as the total duration.
+#[derive(Debug, Clone)]
+pub struct Schedule<Id> {
+    /// Work done by every worker, ordered by start time
+    pub workers: Vec<Vec<Interval<Id>>>,
+    /// Everything that happened to the workers, in order
+    pub events: Vec<Event<Id>>,
+    /// Seconds until the last step was finished
+    pub duration: u64,
+    /// Longest chain of dependent steps, which no number of workers at normal
+    /// speed can finish any faster
+    pub critical_path: Vec<Id>,
+    /// Seconds it takes to work through the critical path
+    pub critical_duration: u64,
+}
+
+impl<Id: Display> Schedule<Id> {
+    /// Fraction of the total duration every worker spent busy.
+    pub fn utilisation(&self) -> Vec<f64> {
+        self.workers.iter()
+            .map(|intervals| {
+                let busy: u64 = intervals.iter().map(|i| i.end - i.start).sum();
+                if self.duration == 0 {
+                    0.0
+                } else {
+                    busy as f64 / self.duration as f64
+                }
+            })
+            .collect()
+    }
+
+    /// One `worker,step,start,end` row per interval, with a header.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("worker,step,start,end\n");
+        for (worker, intervals) in self.workers.iter().enumerate() {
+            for interval in intervals {
+                writeln!(csv, "{},{},{},{}", worker, csv_field(&interval.step.to_string()),
+                         interval.start, interval.end).unwrap();
+            }
+        }
+        csv
+    }
+
+    /// Text chart with one row per worker and `width` columns for the time
+    /// axis, followed by the utilisation of the worker.
+    ///
+    /// Every interval is drawn as `[` followed by as much of the step id as
+    /// fits and padded with `=`, idle time is drawn as `.`.
+    pub fn gantt(&self, width: usize) -> String {
+        let width = width.max(1);
+        let scale = (self.duration as f64 / width as f64).max(1.0);
+        let column = |time: u64| ((time as f64 / scale) as usize).min(width);
+        let label_width = format!("worker {}", self.workers.len().saturating_sub(1)).len();
+        let mut chart = String::new();
+        for (worker, (intervals, utilisation))
+                in self.workers.iter().zip(self.utilisation()).enumerate() {
+            let mut cells = vec!['.'; column(self.duration)];
+            for interval in intervals {
+                let (start, end) = (column(interval.start), column(interval.end));
+                let label = format!("[{}", interval.step);
+                for (cell, chr) in cells[start..end].iter_mut()
+                        .zip(label.chars().chain(std::iter::repeat('='))) {
+                    *cell = chr;
+                }
+            }
+            writeln!(chart, "{:<w$} |{}| {:5.1}%", format!("worker {}", worker),
+                     cells.into_iter().collect::<String>(), utilisation * 100.0,
+                     w = label_width).unwrap();
+        }
+        let axis_end = self.duration.to_string();
+        writeln!(chart, "{:<w$}0{:>pad$}", "", axis_end, w = label_width + 2,
+                 pad = column(self.duration).saturating_sub(1).max(axis_end.len())).unwrap();
+        chart
+    }
+}
+
+impl<Id> Display for Schedule<Id> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.duration)
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,461 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap};
+use std::fmt::Display;
+use std::hash::Hash;
+
+use aoc_common::{err, Result};
+
+use crate::events::{Event, EventKind};
+use crate::schedule::{Interval, Schedule};
+use crate::worker::{LowestId, Worker, WorkerPolicy};
+
+/// Assigns every step the number of seconds it takes to complete.
+pub trait CostModel<Id> {
+    /// Duration of `step`, or `None` if the model does not know the step.
+    fn duration(&self, step: &Id) -> Option<u32>;
+}
+
+impl<Id, F: Fn(&Id) -> u32> CostModel<Id> for F {
+    fn duration(&self, step: &Id) -> Option<u32> {
+        Some(self(step))
+    }
+}
+
+/// Explicit durations for every step.
+impl<Id: Hash + Eq> CostModel<Id> for HashMap<Id, u32> {
+    fn duration(&self, step: &Id) -> Option<u32> {
+        self.get(step).cloned()
+    }
+}
+
+/// The puzzle's cost model: step `A` takes one second, `B` two seconds and so
+/// on up to `Z`, plus a fixed base duration for every step.
+pub struct LetterCost {
+    pub base: u32,
+}
+
+impl<Id: AsRef<str>> CostModel<Id> for LetterCost {
+    fn duration(&self, step: &Id) -> Option<u32> {
+        match step.as_ref().as_bytes() {
+            [c @ b'A'..=b'Z'] => Some(u32::from(c - b'A') + 1 + self.base),
+            _ => None,
+        }
+    }
+}
+
+/// Dependencies between steps, which are referred to by their index in the
+/// sorted list of step ids.
+struct Graph {
+    successors: Vec<Vec<usize>>,
+    in_degrees: Vec<usize>,
+}
+
+/// Orders steps that depend on each other and simulates working on them in
+/// parallel.
+///
+/// When several steps are available at the same time, the smallest id is
+/// picked first.
+#[derive(Debug, Clone)]
+pub struct Scheduler<Id> {
+    steps: BTreeSet<Id>,
+    /// `(before, after)` pairs
+    dependencies: Vec<(Id, Id)>,
+    /// Skills a worker needs to work on a step
+    requirements: BTreeMap<Id, BTreeSet<String>>,
+}
+
+impl<Id: Ord + Clone + Display> Scheduler<Id> {
+    pub fn new() -> Scheduler<Id> {
+        Scheduler { steps: BTreeSet::new(), dependencies: Vec::new(), requirements: BTreeMap::new() }
+    }
+
+    pub fn add_step(&mut self, step: Id) {
+        self.steps.insert(step);
+    }
+
+    /// Require `before` to be finished before `after` can begin.
+    pub fn add_dependency(&mut self, before: Id, after: Id) {
+        self.add_step(before.clone());
+        self.add_step(after.clone());
+        self.dependencies.push((before, after));
+    }
+
+    /// Only let workers with `skill` work on `step`.
+    pub fn require_skill(&mut self, step: Id, skill: &str) {
+        self.add_step(step.clone());
+        self.requirements.entry(step).or_default().insert(skill.to_string());
+    }
+
+    pub fn steps(&self) -> impl Iterator<Item=&Id> {
+        self.steps.iter()
+    }
+
+    /// The known step equal to `step`.
+    pub(crate) fn step(&self, step: &Id) -> Option<&Id> {
+        self.steps.get(step)
+    }
+
+    pub fn dependencies(&self) -> &[(Id, Id)] {
+        &self.dependencies
+    }
+
+    /// Skills a worker needs to work on `step`.
+    pub fn required_skills(&self, step: &Id) -> Option<&BTreeSet<String>> {
+        self.requirements.get(step)
+    }
+
+    fn ids(&self) -> Vec<&Id> {
+        self.steps.iter().collect()
+    }
+
+    fn graph(&self, ids: &[&Id]) -> Graph {
+        let index = |step: &Id| ids.binary_search(&step).unwrap();
+        let mut graph = Graph {
+            successors: vec![Vec::new(); ids.len()],
+            in_degrees: vec![0; ids.len()],
+        };
+        for (before, after) in &self.dependencies {
+            let after = index(after);
+            graph.successors[index(before)].push(after);
+            graph.in_degrees[after] += 1;
+        }
+        graph
+    }
+
+    /// Steps that neither depend on other steps nor are depended upon.
+    pub fn isolated_steps(&self) -> Vec<&Id> {
+        let ids = self.ids();
+        let graph = self.graph(&ids);
+        ids.into_iter()
+            .enumerate()
+            .filter(|&(idx, _)| graph.in_degrees[idx] == 0 && graph.successors[idx].is_empty())
+            .map(|(_, step)| step)
+            .collect()
+    }
+
+    /// Find a cycle in the dependencies, returned as the path `a -> b -> a`
+    /// with the first step repeated at the end.
+    pub fn find_cycle(&self) -> Option<Vec<Id>> {
+        let ids = self.ids();
+        let graph = self.graph(&ids);
+        let mut on_path = vec![false; ids.len()];
+        let mut finished = vec![false; ids.len()];
+        for start in 0..ids.len() {
+            if finished[start] {
+                continue;
+            }
+            // Depth-first search with an explicit stack, so that long chains
+            // of steps can't overflow the call stack. `path` holds the steps
+            // currently being visited along with the index of the next
+            // successor to look at.
+            let mut path: Vec<(usize, usize)> = vec![(start, 0)];
+            on_path[start] = true;
+            while let Some((step, idx)) = path.last_mut() {
+                match graph.successors[*step].get(*idx) {
+                    Some(&next) => {
+                        *idx += 1;
+                        if on_path[next] {
+                            let pos = path.iter().position(|&(s, _)| s == next).unwrap();
+                            let mut cycle: Vec<Id> = path[pos..].iter()
+                                .map(|&(s, _)| ids[s].clone())
+                                .collect();
+                            cycle.push(ids[next].clone());
+                            return Some(cycle);
+                        }
+                        if !finished[next] {
+                            on_path[next] = true;
+                            path.push((next, 0));
+                        }
+                    },
+                    None => {
+                        finished[*step] = true;
+                        on_path[*step] = false;
+                        path.pop();
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Steps that can never begin, because they are part of a cycle or
+    /// depend on a step that is.
+    pub fn unreachable_steps(&self) -> Vec<&Id> {
+        let ids = self.ids();
+        let mut graph = self.graph(&ids);
+        let mut ready: Vec<usize> = (0..ids.len())
+            .filter(|&idx| graph.in_degrees[idx] == 0)
+            .collect();
+        while let Some(step) = ready.pop() {
+            for &next in &graph.successors[step] {
+                graph.in_degrees[next] -= 1;
+                if graph.in_degrees[next] == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+        ids.into_iter()
+            .enumerate()
+            .filter(|&(idx, _)| graph.in_degrees[idx] > 0)
+            .map(|(_, step)| step)
+            .collect()
+    }
+
+    /// Make sure that all steps can be completed.
+    pub fn validate(&self) -> Result<()> {
+        let cycle = match self.find_cycle() {
+            Some(cycle) => cycle,
+            None        => return Ok(()),
+        };
+        let path: Vec<String> = cycle.iter().map(|s| s.to_string()).collect();
+        let blocked: Vec<String> = self.unreachable_steps().into_iter()
+            .filter(|s| !cycle.contains(s))
+            .map(|s| s.to_string())
+            .collect();
+        if blocked.is_empty() {
+            err!("Steps form a cycle: {}", path.join(" -> "))
+        } else {
+            err!("Steps form a cycle: {}; blocked by it: {}",
+                 path.join(" -> "), blocked.join(", "))
+        }
+    }
+
+    /// Order in which a single worker completes the steps.
+    ///
+    /// This is a topological sort with Kahn's algorithm, where a min-heap of
+    /// the available steps makes sure that the smallest id goes first.
+    pub fn order(&self) -> Result<Vec<Id>> {
+        self.validate()?;
+        let ids = self.ids();
+        let mut graph = self.graph(&ids);
+        // Step indices are sorted like the ids, so the smallest index is the
+        // smallest id.
+        let mut available: BinaryHeap<Reverse<usize>> = (0..ids.len())
+            .filter(|&idx| graph.in_degrees[idx] == 0)
+            .map(Reverse)
+            .collect();
+        let mut order: Vec<Id> = Vec::with_capacity(ids.len());
+        while let Some(Reverse(step)) = available.pop() {
+            order.push(ids[step].clone());
+            for &next in &graph.successors[step] {
+                graph.in_degrees[next] -= 1;
+                if graph.in_degrees[next] == 0 {
+                    available.push(Reverse(next));
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    fn durations<C: CostModel<Id>>(ids: &[&Id], cost: &C) -> Result<Vec<u64>> {
+        ids.iter()
+            .map(|step| match cost.duration(step) {
+                Some(duration) => Ok(u64::from(duration)),
+                None => err!("No duration known for step {}", step),
+            })
+            .collect()
+    }
+
+    /// Longest chain of dependent steps and the time it takes to complete.
+    pub fn critical_path<C: CostModel<Id>>(&self, cost: &C) -> Result<(Vec<Id>, u64)> {
+        let order = self.order()?;
+        let ids = self.ids();
+        let index = |step: &Id| ids.binary_search(&step).unwrap();
+        let durations = Scheduler::durations(&ids, cost)?;
+        let graph = self.graph(&ids);
+        // Earliest time every step can be finished and the predecessor that
+        // held it up the longest
+        let mut finished = vec![0; ids.len()];
+        let mut held_up_by: Vec<Option<usize>> = vec![None; ids.len()];
+        for step in order.iter().map(index) {
+            finished[step] += durations[step];
+            for &next in &graph.successors[step] {
+                if finished[step] > finished[next] || held_up_by[next].is_none() {
+                    finished[next] = finished[step];
+                    held_up_by[next] = Some(step);
+                }
+            }
+        }
+        let last = match (0..ids.len()).max_by_key(|&idx| (finished[idx], Reverse(idx))) {
+            Some(last) => last,
+            None       => return Ok((Vec::new(), 0)),
+        };
+        let mut path = vec![ids[last].clone()];
+        let mut step = last;
+        while let Some(prev) = held_up_by[step] {
+            path.push(ids[prev].clone());
+            step = prev;
+        }
+        path.reverse();
+        Ok((path, finished[last]))
+    }
+
+    /// Simulate `num_workers` identical workers completing all steps, with
+    /// work handed to the idle worker with the lowest number.
+    pub fn run<C: CostModel<Id>>(&self, num_workers: usize, cost: &C) -> Result<Schedule<Id>> {
+        self.run_with(&vec![Worker::new(); num_workers], cost, &mut LowestId)
+    }
+
+    /// Simulate `workers` completing all steps, letting `policy` choose among
+    /// the idle workers able to do a step.
+    ///
+    /// Whenever workers are idle, the available steps are handed out in order
+    /// of their ids, skipping steps that none of the idle workers can do.
+    /// All steps finishing at the same time are finished before new work is
+    /// handed out, and a worker left without work afterwards is logged as
+    /// idle.
+    ///
+    /// Instead of ticking through every second, time jumps straight to the
+    /// next moment a worker finishes its step.
+    pub fn run_with<C, P>(&self, workers: &[Worker], cost: &C, policy: &mut P)
+            -> Result<Schedule<Id>>
+            where C: CostModel<Id>, P: WorkerPolicy + ?Sized {
+        if workers.is_empty() {
+            return err!("At least one worker is needed");
+        }
+        if let Some(idx) = workers.iter().position(|w| !(w.speed > 0.0 && w.speed.is_finite())) {
+            return err!("Worker {} needs a positive speed, not {}", idx, workers[idx].speed);
+        }
+        let (critical_path, critical_duration) = self.critical_path(cost)?;
+        let ids = self.ids();
+        let durations = Scheduler::durations(&ids, cost)?;
+        let no_skills = BTreeSet::new();
+        let required: Vec<&BTreeSet<String>> = ids.iter()
+            .map(|step| self.required_skills(step).unwrap_or(&no_skills))
+            .collect();
+        if let Some(idx) = (0..ids.len()).find(|&idx| !workers.iter().any(|w| w.can_do(required[idx]))) {
+            return err!("No worker has the skills needed for step {}", ids[idx]);
+        }
+        let mut graph = self.graph(&ids);
+        let mut available: BTreeSet<usize> = (0..ids.len())
+            .filter(|&idx| graph.in_degrees[idx] == 0)
+            .collect();
+        // Steps being worked on, by the time they will be finished
+        let mut in_progress: BinaryHeap<Reverse<(u64, usize, usize)>> = BinaryHeap::new();
+        let mut idle = vec![true; workers.len()];
+        let mut intervals: Vec<Vec<Interval<Id>>> = vec![Vec::new(); workers.len()];
+        let mut events = Vec::new();
+        // Workers that became idle at the current time
+        let mut freed: Vec<usize> = (0..workers.len()).collect();
+        let mut time: u64 = 0;
+        loop {
+            let mut assigned = Vec::new();
+            for &step in &available {
+                if !idle.contains(&true) {
+                    break;
+                }
+                let candidates: Vec<usize> = (0..workers.len())
+                    .filter(|&worker| idle[worker] && workers[worker].can_do(required[step]))
+                    .collect();
+                if candidates.is_empty() {
+                    continue;
+                }
+                let worker = policy.choose(&candidates, workers);
+                if !candidates.contains(&worker) {
+                    return err!("Worker {} was chosen for step {}, but cannot take it",
+                                worker, ids[step]);
+                }
+                let end = time + workers[worker].time_for(durations[step]);
+                idle[worker] = false;
+                intervals[worker].push(Interval { step: ids[step].clone(), start: time, end });
+                in_progress.push(Reverse((end, step, worker)));
+                events.push(Event { tick: time, worker, event: EventKind::Start, step: Some(ids[step].clone()) });
+                assigned.push(step);
+            }
+            for step in assigned {
+                available.remove(&step);
+            }
+            for worker in freed.drain(..).filter(|&worker| idle[worker]) {
+                events.push(Event { tick: time, worker, event: EventKind::Idle, step: None });
+            }
+            match in_progress.peek() {
+                Some(&Reverse((finished_at, _, _))) => time = finished_at,
+                None => break,
+            }
+            // Finish every step that is done by now before handing out new
+            // work, so that all steps this unblocks compete for the workers
+            while let Some(&Reverse((finished_at, step, worker))) = in_progress.peek() {
+                if finished_at > time {
+                    break;
+                }
+                in_progress.pop();
+                idle[worker] = true;
+                freed.push(worker);
+                events.push(Event { tick: time, worker, event: EventKind::Finish, step: Some(ids[step].clone()) });
+                for &next in &graph.successors[step] {
+                    graph.in_degrees[next] -= 1;
+                    if graph.in_degrees[next] == 0 {
+                        available.insert(next);
+                    }
+                }
+            }
+        }
+        Ok(Schedule { workers: intervals, events, duration: time, critical_path, critical_duration })
+    }
+
+    /// Total duration for every number of workers from one to `max_workers`.
+    pub fn sweep<C: CostModel<Id>>(&self, max_workers: usize, cost: &C) -> Result<Vec<u64>> {
+        (1..=max_workers)
+            .map(|num_workers| self.run(num_workers, cost).map(|schedule| schedule.duration))
+            .collect()
+    }
+}
+
+impl<Id: Ord + Clone + Display> Default for Scheduler<Id> {
+    fn default() -> Scheduler<Id> {
+        Scheduler::new()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduler(dependencies: &[(char, char)]) -> Scheduler<char> {
+        let mut scheduler = Scheduler::new();
+        for &(before, after) in dependencies {
+            scheduler.add_dependency(before, after);
+        }
+        scheduler
+    }
+
+    #[test]
+    fn reports_cycle_path() {
+        let scheduler = scheduler(&[('A', 'C'), ('C', 'F'), ('F', 'C'), ('F', 'E'), ('B', 'E')]);
+        assert_eq!(Some(vec!['C', 'F', 'C']), scheduler.find_cycle());
+        assert_eq!(vec![&'C', &'E', &'F'], scheduler.unreachable_steps());
+        assert_eq!("invalid puzzle: Steps form a cycle: C -> F -> C; blocked by it: E",
+                   scheduler.order().unwrap_err().to_string());
+    }
+
+    #[test]
+    fn reports_self_dependency() {
+        let scheduler = scheduler(&[('A', 'A')]);
+        assert_eq!(Some(vec!['A', 'A']), scheduler.find_cycle());
+        assert!(scheduler.run(2, &|_: &char| 1).is_err());
+    }
+
+    #[test]
+    fn assigns_steps_to_skilled_workers() {
+        let mut scheduler = scheduler(&[('A', 'C'), ('B', 'C')]);
+        scheduler.require_skill('B', "paint");
+        let workers = [Worker::new().with_speed(2.0), Worker::new().with_skill("paint")];
+        let schedule = scheduler.run_with(&workers, &|_: &char| 4, &mut LowestId).unwrap();
+        let steps = |worker: usize| -> Vec<char> {
+            schedule.workers[worker].iter().map(|i| i.step).collect()
+        };
+        assert_eq!((vec!['A', 'C'], vec!['B']), (steps(0), steps(1)));
+        assert_eq!(6, schedule.duration);
+        assert!(scheduler.run(2, &|_: &char| 4).is_err());
+    }
+
+    #[test]
+    fn finds_isolated_steps() {
+        let mut scheduler = scheduler(&[('A', 'B')]);
+        scheduler.add_step('Z');
+        assert_eq!(None, scheduler.find_cycle());
+        assert_eq!(vec![&'Z'], scheduler.isolated_steps());
+        assert!(scheduler.unreachable_steps().is_empty());
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::BTreeSet;
+
+/// Someone working on the steps, at their own pace and with their own set of
+/// skills.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Worker {
+    /// Multiplier for the working pace, a worker with speed `2.0` finishes
+    /// steps in half the time
+    pub speed: f64,
+    pub skills: BTreeSet<String>,
+}
+
+impl Worker {
+    /// Worker with normal speed and no skills.
+    pub fn new() -> Worker {
+        Worker { speed: 1.0, skills: BTreeSet::new() }
+    }
+
+    pub fn with_speed(mut self, speed: f64) -> Worker {
+        self.speed = speed;
+        self
+    }
+
+    pub fn with_skill(mut self, skill: &str) -> Worker {
+        self.skills.insert(skill.to_string());
+        self
+    }
+
+    /// Whether the worker has all of the `required` skills.
+    pub fn can_do(&self, required: &BTreeSet<String>) -> bool {
+        required.is_subset(&self.skills)
+    }
+
+    /// Seconds the worker needs for a step that takes `duration` seconds at
+    /// normal speed, rounded up.
+    pub fn time_for(&self, duration: u64) -> u64 {
+        (duration as f64 / self.speed).ceil() as u64
+    }
+}
+
+impl Default for Worker {
+    fn default() -> Worker {
+        Worker::new()
+    }
+}
+
+/// Decides which of the idle workers gets to work on the next step.
+pub trait WorkerPolicy {
+    /// Pick one of the `candidates`, the ascending indices of the idle
+    /// workers in `workers` that are able to do the step.
+    fn choose(&mut self, candidates: &[usize], workers: &[Worker]) -> usize;
+}
+
+impl<F: FnMut(&[usize], &[Worker]) -> usize> WorkerPolicy for F {
+    fn choose(&mut self, candidates: &[usize], workers: &[Worker]) -> usize {
+        self(candidates, workers)
+    }
+}
+
+/// Always pick the worker with the lowest number.
+pub struct LowestId;
+
+impl WorkerPolicy for LowestId {
+    fn choose(&mut self, candidates: &[usize], _workers: &[Worker]) -> usize {
+        candidates[0]
+    }
+}
+
+/// Pick the fastest worker, or the one with the lowest number among equally
+/// fast workers.
+pub struct FastestFirst;
+
+impl WorkerPolicy for FastestFirst {
+    fn choose(&mut self, candidates: &[usize], workers: &[Worker]) -> usize {
+        let mut best = candidates[0];
+        for &worker in &candidates[1..] {
+            if workers[worker].speed > workers[best].speed {
+                best = worker;
+            }
+        }
+        best
+    }
+}
+
+/// Take turns, picking the first candidate after the previously picked
+/// worker.
+#[derive(Default)]
+pub struct RoundRobin {
+    next: usize,
+}
+
+impl WorkerPolicy for RoundRobin {
+    fn choose(&mut self, candidates: &[usize], _workers: &[Worker]) -> usize {
+        let worker = candidates.iter()
+            .find(|&&worker| worker >= self.next)
+            .unwrap_or(&candidates[0]);
+        self.next = worker + 1;
+        *worker
+    }
+}
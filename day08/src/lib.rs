@@ -0,0 +1,61 @@
+use aoc_common::{collect_errors, err, parse_token, tokens, Error, Result, Solution};
+
+mod tree;
+
+pub use crate::tree::{Node, TreeError, Validation};
+
+/// Tree specification on the first line of the input.
+struct Spec<'a> {
+    line: &'a str,
+    /// Tokens of the numbers along with their offsets in the line
+    tokens: Vec<(usize, &'a str)>,
+    numbers: Vec<u32>,
+}
+
+impl<'a> Spec<'a> {
+    fn read(input: &'a str) -> Result<Spec<'a>> {
+        let line = match input.lines().next() {
+            Some(l) => Ok(l),
+            None    => err!("No tree specification passed.")
+        }?;
+        let tokens: Vec<(usize, &str)> = tokens(line).collect();
+        let numbers = collect_errors(tokens.iter().map(|&(start, c)| parse_token(c, start)))
+            .map_err(|e| e.at_line(1, line))?;
+        Ok(Spec { line, tokens, numbers })
+    }
+
+    /// Point `error` at the number it refers to, or at the end of the line.
+    fn locate(&self, error: &TreeError) -> Error {
+        let error = match self.tokens.get(error.position()) {
+            Some(&(start, c)) => Error::parse(start, c, error.to_string()),
+            None              => Error::parse(self.line.trim_end().len(), "", error.to_string()),
+        };
+        error.at_line(1, self.line)
+    }
+}
+
+/// Numbers of the tree specification on the first line of `input`.
+pub fn numbers(input: &str) -> Result<Vec<u32>> {
+    Spec::read(input).map(|spec| spec.numbers)
+}
+
+impl Solution for Node {
+    type Part1Answer = u64;
+    type Part2Answer = u64;
+
+    fn parse(input: &str) -> Result<Node> {
+        let spec = Spec::read(input)?;
+        Node::from_slice_with(&spec.numbers, Validation::Lenient)
+            .map_err(|e| spec.locate(&e))
+    }
+
+    /// Sum of all metadata entries in the tree
+    fn part1(&self) -> Result<u64> {
+        Ok(self.sum_metadata())
+    }
+
+    /// Value of the root node
+    fn part2(&self) -> Result<u64> {
+        Ok(self.value())
+    }
+}
@@ -0,0 +1,373 @@
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+
+/// Tree read from a flat list of numbers, where every node is given as the
+/// number of its children, the number of its metadata entries, the children
+/// and finally the metadata entries.
+pub struct Node<T = u32> {
+    metadata: Vec<T>,
+    children: Vec<Node<T>>,
+}
+
+/// Why a list of numbers does not describe a tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeError {
+    /// The numbers ended at `position` while `expected` was still missing
+    Truncated { position: usize, expected: &'static str },
+    /// `count` numbers were left over after the root node, starting at
+    /// `position`
+    TrailingData { position: usize, count: usize },
+    /// The metadata entry at `position` of a node with children is 0, which
+    /// references no child
+    ZeroReference { position: usize },
+    /// The metadata entry at `position` references `child`, but the node only
+    /// has `children` children
+    MissingChild { position: usize, child: u64, children: usize },
+}
+
+/// How to deal with numbers that describe a tree but not a meaningful one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Validation {
+    /// Reject references to missing children and numbers after the root node
+    Strict,
+    /// Follow the puzzle: references to missing children count as zero and
+    /// numbers after the root node are ignored
+    Lenient,
+}
+
+impl TreeError {
+    /// Zero-based position in the list of numbers the error refers to.
+    pub fn position(&self) -> usize {
+        match *self {
+            TreeError::Truncated { position, .. }    => position,
+            TreeError::TrailingData { position, .. } => position,
+            TreeError::ZeroReference { position }    => position,
+            TreeError::MissingChild { position, .. } => position,
+        }
+    }
+}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TreeError::Truncated { expected, .. } =>
+                write!(f, "input ends before {}", expected),
+            TreeError::TrailingData { count, .. } =>
+                write!(f, "{} number(s) left after the root node", count),
+            TreeError::ZeroReference { .. } =>
+                write!(f, "metadata entry 0 references no child"),
+            TreeError::MissingChild { child, children, .. } =>
+                write!(f, "metadata entry references child {}, but the node has {} child(ren)",
+                       child, children),
+        }
+    }
+}
+
+impl error::Error for TreeError {}
+
+/// Numbers of a tree along with the position of the next number.
+struct Numbers<I> {
+    numbers: I,
+    position: usize,
+}
+
+impl<T: Into<u64>, I: Iterator<Item=T>> Numbers<I> {
+    fn next(&mut self, expected: &'static str) -> Result<T, TreeError> {
+        let number = self.numbers.next()
+            .ok_or(TreeError::Truncated { position: self.position, expected })?;
+        self.position += 1;
+        Ok(number)
+    }
+
+    /// Header of the next node.
+    fn partial<N>(&mut self) -> Result<Partial<N>, TreeError> {
+        let mut count = |expected| {
+            self.next(expected).map(|n| usize::try_from(n.into()).unwrap_or(usize::MAX))
+        };
+        Ok(Partial {
+            num_children: count("the number of children")?,
+            num_metadata: count("the number of metadata entries")?,
+            children: Vec::new(),
+        })
+    }
+}
+
+/// Node whose children are still being read.
+struct Partial<T> {
+    num_children: usize,
+    num_metadata: usize,
+    children: Vec<Node<T>>,
+}
+
+impl<T: Copy + Into<u64>> Node<T> {
+    pub fn new(metadata: Vec<T>, children: Vec<Node<T>>) -> Node<T> {
+        Node { metadata, children }
+    }
+
+    /// Read a tree from `numbers`, all of which must belong to it and whose
+    /// metadata entries may only reference existing children.
+    ///
+    /// The nodes are kept on an explicit stack, so the depth of the tree is
+    /// only limited by the available memory.
+    pub fn from_numbers<I: IntoIterator<Item=T>>(numbers: I) -> Result<Node<T>, TreeError> {
+        Node::from_numbers_with(numbers, Validation::Strict)
+    }
+
+    pub fn from_numbers_with<I: IntoIterator<Item=T>>(
+            numbers: I, validation: Validation) -> Result<Node<T>, TreeError> {
+        match validation {
+            Validation::Strict  => Node::read(numbers, &mut Err),
+            Validation::Lenient => Node::read(numbers, &mut |_| Ok(())),
+        }
+    }
+
+    pub fn from_slice(numbers: &[T]) -> Result<Node<T>, TreeError> {
+        Node::from_numbers(numbers.iter().cloned())
+    }
+
+    pub fn from_slice_with(numbers: &[T], validation: Validation) -> Result<Node<T>, TreeError> {
+        Node::from_numbers_with(numbers.iter().cloned(), validation)
+    }
+
+    /// Everything the strict mode would reject in `numbers`, in the order of
+    /// the numbers, followed by the truncation of the tree if there is one.
+    pub fn validate<I: IntoIterator<Item=T>>(numbers: I) -> Vec<TreeError> {
+        let mut issues = Vec::new();
+        if let Err(e) = Node::read(numbers, &mut |issue| { issues.push(issue); Ok(()) }) {
+            issues.push(e);
+        }
+        issues
+    }
+
+    /// Read a tree, passing anything a strict reader would reject to
+    /// `report`, which decides whether to go on.
+    fn read<I, R>(numbers: I, report: &mut R) -> Result<Node<T>, TreeError>
+            where I: IntoIterator<Item=T>, R: FnMut(TreeError) -> Result<(), TreeError> {
+        let mut numbers = Numbers { numbers: numbers.into_iter(), position: 0 };
+        let mut stack = vec![numbers.partial()?];
+        let root = loop {
+            let top = stack.last_mut().unwrap();
+            if top.children.len() < top.num_children {
+                stack.push(numbers.partial()?);
+                continue;
+            }
+            let Partial { num_metadata, children, .. } = stack.pop().unwrap();
+            let mut metadata = Vec::with_capacity(num_metadata.min(1024));
+            for _ in 0..num_metadata {
+                let position = numbers.position;
+                let entry = numbers.next("a metadata entry")?;
+                match entry.into() {
+                    _ if children.is_empty() => Ok(()),
+                    0 => report(TreeError::ZeroReference { position }),
+                    child if child > children.len() as u64 =>
+                        report(TreeError::MissingChild { position, child, children: children.len() }),
+                    _ => Ok(()),
+                }?;
+                metadata.push(entry);
+            }
+            let node = Node { metadata, children };
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None         => break node,
+            }
+        };
+        match numbers.numbers.count() {
+            0     => Ok(root),
+            count => report(TreeError::TrailingData { position: numbers.position, count })
+                .map(|_| root),
+        }
+    }
+
+    pub fn metadata(&self) -> &[T] {
+        &self.metadata
+    }
+
+    pub fn children(&self) -> &[Node<T>] {
+        &self.children
+    }
+
+    /// All nodes of the tree, parents before their children.
+    pub fn iter(&self) -> impl Iterator<Item=&Node<T>> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+
+    pub fn sum_metadata(&self) -> u64 {
+        self.iter()
+            .flat_map(|node| node.metadata.iter())
+            .map(|&entry| entry.into())
+            .sum()
+    }
+
+    /// Sum of the metadata for a leaf, otherwise the sum of the values of the
+    /// children referenced by the 1-based metadata entries, where references
+    /// to missing children count as zero.
+    pub fn value(&self) -> u64 {
+        // Nodes along with the values of those children already computed
+        let mut stack: Vec<(&Node<T>, Vec<u64>)> = vec![(self, Vec::new())];
+        loop {
+            let (node, values) = stack.last().unwrap();
+            if let Some(child) = node.children.get(values.len()) {
+                stack.push((child, Vec::with_capacity(child.children.len())));
+                continue;
+            }
+            let (node, values) = stack.pop().unwrap();
+            let value = if node.children.is_empty() {
+                node.metadata.iter().map(|&entry| entry.into()).sum()
+            } else {
+                node.metadata.iter()
+                    .filter_map(|&entry| usize::try_from(entry.into()).ok()?.checked_sub(1))
+                    .filter_map(|idx| values.get(idx))
+                    .sum()
+            };
+            match stack.last_mut() {
+                Some((_, parent_values)) => parent_values.push(value),
+                None                     => return value,
+            }
+        }
+    }
+}
+
+/// Takes the children apart one level at a time, dropping a deep tree
+/// recursively would overflow the stack.
+impl<T> Drop for Node<T> {
+    fn drop(&mut self) {
+        let mut stack = std::mem::take(&mut self.children);
+        while let Some(mut node) = stack.pop() {
+            stack.append(&mut node.children);
+        }
+    }
+}
+
+/// Copies the tree bottom-up with an explicit stack, like `value`.
+impl<T: Clone> Clone for Node<T> {
+    fn clone(&self) -> Node<T> {
+        // Nodes along with the copies of those children already made
+        let mut stack: Vec<(&Node<T>, Vec<Node<T>>)> = vec![(self, Vec::new())];
+        loop {
+            let (node, children) = stack.last().unwrap();
+            if let Some(child) = node.children.get(children.len()) {
+                stack.push((child, Vec::with_capacity(child.children.len())));
+                continue;
+            }
+            let (node, children) = stack.pop().unwrap();
+            let copy = Node { metadata: node.metadata.clone(), children };
+            match stack.last_mut() {
+                Some((_, siblings)) => siblings.push(copy),
+                None                => return copy,
+            }
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Node<T> {
+    fn eq(&self, other: &Node<T>) -> bool {
+        let mut stack = vec![(self, other)];
+        while let Some((a, b)) = stack.pop() {
+            if a.metadata != b.metadata || a.children.len() != b.children.len() {
+                return false;
+            }
+            stack.extend(a.children.iter().zip(&b.children));
+        }
+        true
+    }
+}
+
+impl<T: Eq> Eq for Node<T> {}
+
+/// Same output as a derived `Debug` without the alternate form, written
+/// from an explicit stack.
+impl<T: fmt::Debug> fmt::Debug for Node<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        enum Part<'a, T> {
+            Node(&'a Node<T>),
+            Text(&'static str),
+        }
+        let mut stack = vec![Part::Node(self)];
+        while let Some(part) = stack.pop() {
+            match part {
+                Part::Text(text) => f.write_str(text)?,
+                Part::Node(node) => {
+                    write!(f, "Node {{ metadata: {:?}, children: [", node.metadata)?;
+                    stack.push(Part::Text("] }"));
+                    for (idx, child) in node.children.iter().enumerate().rev() {
+                        stack.push(Part::Node(child));
+                        if idx > 0 {
+                            stack.push(Part::Text(", "));
+                        }
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [u32; 16] = [2, 3, 0, 3, 10, 11, 12, 1, 1, 0, 1, 99, 2, 1, 1, 2];
+
+    #[test]
+    fn solves_example() {
+        let tree = Node::from_slice_with(&EXAMPLE, Validation::Lenient).unwrap();
+        assert_eq!(4, tree.iter().count());
+        assert_eq!(138, tree.sum_metadata());
+        assert_eq!(66, tree.value());
+    }
+
+    #[test]
+    fn reports_truncated_and_trailing_numbers() {
+        assert_eq!(Err(TreeError::Truncated { position: 13, expected: "a metadata entry" }),
+                   Node::from_slice_with(&EXAMPLE[..13], Validation::Lenient));
+        assert_eq!(Err(TreeError::Truncated { position: 3, expected: "the number of metadata entries" }),
+                   Node::<u32>::from_slice(&[1, 1, 0]));
+        assert_eq!(Err(TreeError::TrailingData { position: 5, count: 2 }),
+                   Node::from_numbers(EXAMPLE[2..7].iter().cloned().chain(vec![7, 8])));
+    }
+
+    #[test]
+    fn validates_references_and_trailing_data() {
+        let numbers: [u32; 10] = [1, 3, 0, 1, 7, 0, 1, 2, 4, 4];
+        assert_eq!(vec![
+            TreeError::ZeroReference { position: 5 },
+            TreeError::MissingChild { position: 7, child: 2, children: 1 },
+            TreeError::TrailingData { position: 8, count: 2 },
+        ], Node::validate(numbers.iter().cloned()));
+        assert_eq!(Err(TreeError::ZeroReference { position: 5 }), Node::from_slice(&numbers));
+        let tree = Node::from_slice_with(&numbers, Validation::Lenient).unwrap();
+        assert_eq!(7, tree.value());
+        assert_eq!(vec![
+            TreeError::MissingChild { position: 12, child: 2, children: 1 },
+            TreeError::Truncated { position: 13, expected: "a metadata entry" },
+        ], Node::validate(EXAMPLE[..13].iter().cloned()));
+    }
+
+    #[test]
+    fn handles_deep_trees() {
+        let depth = 100_000;
+        let mut numbers: Vec<u32> = (0..depth).flat_map(|_| vec![1, 1]).collect();
+        numbers.extend(vec![0, 1, 5]);
+        numbers.extend((0..depth).map(|_| 1));
+        let tree = Node::from_slice(&numbers).unwrap();
+        assert_eq!(depth as u64 + 5, tree.sum_metadata());
+        assert_eq!(5, tree.value());
+        let copy = tree.clone();
+        assert!(copy == tree);
+        assert_eq!(format!("{:?}", copy).len(), format!("{:?}", tree).len());
+    }
+
+    #[test]
+    fn formats_like_a_derived_debug() {
+        let tree = Node::<u32>::from_slice(&[2, 2, 0, 1, 7, 1, 0, 0, 1, 0, 1, 2]).unwrap();
+        assert_eq!("Node { metadata: [1, 2], children: [Node { metadata: [7], children: [] }, \
+                    Node { metadata: [], children: [Node { metadata: [0], children: [] }] }] }",
+                   format!("{:?}", tree));
+    }
+}
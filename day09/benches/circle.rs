@@ -0,0 +1,37 @@
+//! Compare the circle backends on the game from part 2, with a hundred times
+//! as many marbles as the puzzle input.
+
+use std::collections::VecDeque;
+use std::hint::black_box;
+
+use circular_list::CircularList;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day09::{Circle, MarbleGame};
+
+const INPUT: &str = include_str!("../input.txt");
+
+fn play<C: Circle>(players: usize, num_marbles: usize) -> usize {
+    let mut game: MarbleGame<C> = MarbleGame::with_circle(players, num_marbles);
+    let mut rounds = 0;
+    while game.play_round().is_some() {
+        rounds += 1;
+    }
+    rounds
+}
+
+fn part2(c: &mut Criterion) {
+    let game: MarbleGame = INPUT.trim().parse().unwrap();
+    let (players, num_marbles) = (game.players(), game.num_marbles() * 100);
+    let mut group = c.benchmark_group("part2");
+    group.sample_size(10);
+    group.bench_function("circular_list", |b| {
+        b.iter(|| play::<CircularList<usize>>(black_box(players), black_box(num_marbles)))
+    });
+    group.bench_function("vec_deque", |b| {
+        b.iter(|| play::<VecDeque<usize>>(black_box(players), black_box(num_marbles)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, part2);
+criterion_main!(benches);
@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+
+use circular_list::CircularList;
+
+/// Marbles placed around a circle, one of which is the current marble.
+pub trait Circle {
+    fn with_capacity(capacity: usize) -> Self;
+
+    /// Make the marble `steps` positions clockwise of the current one the
+    /// current marble, or counter-clockwise if `steps` is negative.
+    fn rotate(&mut self, steps: isize);
+
+    /// Place `marble` clockwise of the current marble and make it the current
+    /// marble.
+    fn insert_after(&mut self, marble: usize);
+
+    /// Take out the current marble, making the marble clockwise of it the
+    /// current one.
+    fn remove(&mut self) -> Option<usize>;
+
+    /// All marbles in clockwise order, starting with the current marble.
+    fn marbles(&self) -> Vec<usize>;
+}
+
+impl Circle for CircularList<usize> {
+    fn with_capacity(capacity: usize) -> CircularList<usize> {
+        CircularList::with_capacity(capacity)
+    }
+
+    fn rotate(&mut self, steps: isize) {
+        CircularList::rotate(self, steps)
+    }
+
+    fn insert_after(&mut self, marble: usize) {
+        CircularList::insert_after(self, marble)
+    }
+
+    fn remove(&mut self) -> Option<usize> {
+        CircularList::remove(self)
+    }
+
+    fn marbles(&self) -> Vec<usize> {
+        self.iter().cloned().collect()
+    }
+}
+
+/// Keeps the marbles in clockwise order with the current marble at the back,
+/// so that moving around the circle is a rotation of the deque.
+impl Circle for VecDeque<usize> {
+    fn with_capacity(capacity: usize) -> VecDeque<usize> {
+        VecDeque::with_capacity(capacity)
+    }
+
+    fn rotate(&mut self, steps: isize) {
+        if self.is_empty() {
+            return;
+        }
+        let distance = steps.unsigned_abs() % self.len();
+        if steps < 0 {
+            self.rotate_right(distance);
+        } else {
+            self.rotate_left(distance);
+        }
+    }
+
+    fn insert_after(&mut self, marble: usize) {
+        self.push_back(marble);
+    }
+
+    fn remove(&mut self) -> Option<usize> {
+        let marble = self.pop_back()?;
+        Circle::rotate(self, 1);
+        Some(marble)
+    }
+
+    fn marbles(&self) -> Vec<usize> {
+        let (current, rest) = match self.back() {
+            Some(&current) => (current, self.range(..self.len() - 1)),
+            None           => return Vec::new(),
+        };
+        std::iter::once(current).chain(rest.cloned()).collect()
+    }
+}
@@ -0,0 +1,469 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use aoc_common::{err, match_tokens, parse_lines, parse_token, Error, Result, Solution};
+use circular_list::CircularList;
+use rayon::prelude::*;
+
+mod circle;
+mod rules;
+mod scoreboard;
+
+pub use crate::circle::Circle;
+pub use crate::rules::Rules;
+pub use crate::scoreboard::{Scoreboard, ScoringEvent, Standing};
+
+
+/// Game played with marbles kept in a circle of type `C`.
+pub struct MarbleGame<C = CircularList<usize>> {
+    marbles: C,
+    /// Score of every player, indexed by player number
+    player_scores: Vec<usize>,
+    /// Every time a player scored, if it is being recorded
+    history: Option<Vec<ScoringEvent>>,
+    rules: Rules,
+    players: usize,
+    num_marbles: usize,
+    next_marble: usize,
+}
+
+/// Tokens of a game description, `None` marks the positions of the numbers.
+const GRAMMAR: [Option<&str>; 8] = [
+    None, Some("players;"), Some("last"), Some("marble"), Some("is"), Some("worth"), None,
+    Some("points"),
+];
+
+/// Number of players and value of the last marble of a game described as
+/// `<N> players; last marble is worth <M> points`.
+fn parse_game(s: &str) -> Result<(usize, usize)> {
+    let numbers = match_tokens(s, &GRAMMAR, "a number", "game")?.into_iter()
+        .map(|(start, token)| match parse_token(token, start)? {
+            0      => Err(Error::parse(start, token, "expected a positive number")),
+            number => Ok(number),
+        })
+        .collect::<Result<Vec<usize>>>()?;
+    Ok((numbers[0], numbers[1]))
+}
+
+impl FromStr for MarbleGame {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<MarbleGame> {
+        let (players, num_marbles) = parse_game(s)?;
+        Ok(MarbleGame::new(players, num_marbles))
+    }
+}
+
+impl MarbleGame {
+    pub fn new(players: usize, num_marbles: usize) -> MarbleGame {
+        MarbleGame::with_circle(players, num_marbles)
+    }
+}
+
+impl<C: Circle> MarbleGame<C> {
+    /// Game that keeps its marbles in a `C`.
+    pub fn with_circle(players: usize, num_marbles: usize) -> MarbleGame<C> {
+        MarbleGame::with_rules(players, num_marbles, Rules::default())
+    }
+
+    /// Game that keeps its marbles in a `C` and is played by other `rules`.
+    pub fn with_rules(players: usize, num_marbles: usize, rules: Rules) -> MarbleGame<C> {
+        // Every scoring marble is kept by the player along with another one
+        // that is removed, so the circle never holds more than this
+        let num_scoring = num_marbles.checked_div(rules.scoring_modulus).unwrap_or(0);
+        let mut marbles = C::with_capacity((num_marbles + 2).saturating_sub(2 * num_scoring));
+        marbles.insert_after(rules.starting_marble);
+        MarbleGame {
+            players,
+            num_marbles,
+            marbles,
+            player_scores: vec![0; players],
+            history: None,
+            rules,
+            next_marble: rules.starting_marble + 1,
+        }
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn players(&self) -> usize {
+        self.players
+    }
+
+    /// Value of the last marble
+    pub fn num_marbles(&self) -> usize {
+        self.num_marbles
+    }
+
+    /// Keep a record of every scoring event from now on.
+    pub fn record_history(&mut self) {
+        self.history.get_or_insert_with(Vec::new);
+    }
+
+    /// Scoring events so far, empty unless `record_history` was called.
+    pub fn history(&self) -> &[ScoringEvent] {
+        self.history.as_deref().unwrap_or(&[])
+    }
+
+    /// Current scores of all players, ranked.
+    pub fn scoreboard(&self) -> Scoreboard {
+        Scoreboard::new(&self.player_scores)
+    }
+
+    /// Play the next marble, returning `None` once the last marble was
+    /// played.
+    pub fn play_round(&mut self) -> Option<usize> {
+        if self.next_marble > self.num_marbles {
+            return None;
+        }
+        let round = self.next_marble - self.rules.starting_marble;
+        let player = round % self.players;
+        if self.next_marble.is_multiple_of(self.rules.scoring_modulus) {
+            self.marbles.rotate(self.rules.removal_offset);
+            let removed = self.marbles.remove();
+            let points = self.next_marble + removed.unwrap_or(0);
+            self.player_scores[player] += points;
+            if let Some(history) = &mut self.history {
+                history.push(ScoringEvent {
+                    round,
+                    player,
+                    placed: self.next_marble,
+                    removed,
+                    points,
+                });
+            }
+        } else {
+            self.marbles.rotate(self.rules.insertion_offset);
+            self.marbles.insert_after(self.next_marble);
+        }
+        self.next_marble += 1;
+        if self.next_marble > self.num_marbles {
+            None
+        } else {
+            Some(self.next_marble)
+        }
+    }
+
+    /// The circle the way the puzzle text shows it, e.g.
+    /// `[3]  0  2  1 (3)`: the player who played the last marble, followed by
+    /// the marbles clockwise from the starting marble, with the current
+    /// marble in parentheses.
+    ///
+    /// Unlike everywhere else, players are numbered from 1 like in the puzzle
+    /// text, `[-]` marks that no marble was played yet.  If the starting
+    /// marble was removed, the circle is shown from the lowest marble.
+    pub fn render(&self) -> String {
+        let mut marbles = self.marbles.marbles();
+        let first = marbles.iter()
+            .position(|&marble| marble == self.rules.starting_marble)
+            .or_else(|| marbles.iter().enumerate().min_by_key(|&(_, m)| m).map(|(idx, _)| idx))
+            .unwrap_or(0);
+        marbles.rotate_left(first);
+        let current = (marbles.len() - first) % marbles.len().max(1);
+        let width = self.num_marbles.max(self.rules.starting_marble).to_string().len();
+        let mut line = if self.next_marble > self.rules.starting_marble + 1 {
+            let player = (self.next_marble - 1 - self.rules.starting_marble) % self.players;
+            format!("[{}]", if player == 0 { self.players } else { player })
+        } else {
+            "[-]".to_string()
+        };
+        for (idx, marble) in marbles.iter().enumerate() {
+            if idx == current {
+                line.push_str(&format!("{:>w$}", format!("({})", marble), w = width + 2));
+            } else if idx > 0 && idx - 1 == current {
+                line.push_str(&format!("{:>w$}", marble, w = width));
+            } else {
+                line.push_str(&format!("{:>w$}", marble, w = width + 1));
+            }
+        }
+        line
+    }
+
+    /// Play up to `rounds` rounds, passing the rendered circle to `show`
+    /// before the first round and after every round.
+    pub fn trace<F: FnMut(&str)>(&mut self, rounds: usize, mut show: F) {
+        show(&self.render());
+        for _ in 0..rounds {
+            if self.next_marble > self.num_marbles {
+                break;
+            }
+            self.play_round();
+            show(&self.render());
+        }
+    }
+
+    /// Play all rounds and return the winner along with their score, see
+    /// `Scoreboard::winner` for how ties are broken.
+    pub fn play_game(&mut self) -> (usize, usize) {
+        self.play_game_with(|_, _| ())
+    }
+
+    /// Play all rounds, calling `progress` with the number of marbles played
+    /// so far and the total number of marbles.
+    ///
+    /// To keep the overhead low, `progress` is called at most a thousand
+    /// times and once more when the game is over.
+    pub fn play_game_with<F: FnMut(usize, usize)>(&mut self, mut progress: F) -> (usize, usize) {
+        let interval = self.num_marbles.div_ceil(1000).max(1);
+        while let Some(next_marble) = self.play_round() {
+            let played = next_marble - 1;
+            if played.is_multiple_of(interval) {
+                progress(played, self.num_marbles);
+            }
+        }
+        progress(self.num_marbles, self.num_marbles);
+        let winner = self.scoreboard().winner().cloned().unwrap();
+        (winner.player, winner.score)
+    }
+}
+
+/// Progress hook for `play_game_with` that shows the percentage of marbles
+/// played on stderr.
+pub fn print_progress(played: usize, total: usize) {
+    eprint!("\r{:.1}%", played as f64 / total as f64 * 100.0);
+    if played == total {
+        eprintln!();
+    }
+}
+
+
+/// High score of a game, displayed in the puzzle's words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameResult {
+    pub players: usize,
+    pub num_marbles: usize,
+    pub high_score: usize,
+    /// Time it took to play the game
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} players; last marble is worth {} points: high score is {}",
+               self.players, self.num_marbles, self.high_score)
+    }
+}
+
+/// Results of all games, displayed as just the high score if there was a
+/// single game and as one line per game otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighScores(pub Vec<GameResult>);
+
+impl fmt::Display for HighScores {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.as_slice() {
+            [result] => write!(f, "{}", result.high_score),
+            results  => {
+                let lines: Vec<String> = results.iter().map(GameResult::to_string).collect();
+                write!(f, "{}", lines.join("\n"))
+            },
+        }
+    }
+}
+
+/// Games to play, one per line of the puzzle input.
+pub struct Games {
+    games: Vec<(usize, usize)>,
+    /// Whether the games show their progress on stderr
+    show_progress: bool,
+}
+
+impl Games {
+    pub fn set_show_progress(&mut self, show_progress: bool) {
+        self.show_progress = show_progress;
+    }
+
+    /// Number of players and value of the last marble of every game.
+    pub fn games(&self) -> &[(usize, usize)] {
+        &self.games
+    }
+
+    /// Play every game with its number of marbles multiplied by `factor`.
+    ///
+    /// The games are played in parallel on rayon's thread pool, unless they
+    /// show their progress, which would garble the output.  Either way the
+    /// results are in the order of the games.
+    pub fn play(&self, factor: usize) -> Vec<GameResult> {
+        let play = |&(players, num_marbles): &(usize, usize)| {
+            let num_marbles = num_marbles * factor;
+            let started = Instant::now();
+            let mut game = MarbleGame::new(players, num_marbles);
+            let (_, high_score) = if self.show_progress {
+                game.play_game_with(print_progress)
+            } else {
+                game.play_game()
+            };
+            GameResult { players, num_marbles, high_score, elapsed: started.elapsed() }
+        };
+        if self.show_progress {
+            self.games.iter().map(play).collect()
+        } else {
+            self.games.par_iter().map(play).collect()
+        }
+    }
+}
+
+impl Solution for Games {
+    type Part1Answer = HighScores;
+    type Part2Answer = HighScores;
+
+    fn parse(input: &str) -> Result<Games> {
+        let games = parse_lines(input, parse_game)?;
+        if games.is_empty() {
+            return err!("No game specification passed.");
+        }
+        Ok(Games { games, show_progress: false })
+    }
+
+    /// Winning score of every game
+    fn part1(&self) -> Result<HighScores> {
+        Ok(HighScores(self.play(1)))
+    }
+
+    /// Winning score of every game with a hundred times as many marbles
+    fn part2(&self) -> Result<HighScores> {
+        Ok(HighScores(self.play(100)))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use aoc_common::test_support::parse_error;
+
+    use super::*;
+
+    macro_rules! marble_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (input, expected) = $value;
+                let mut game: MarbleGame = input.parse().unwrap();
+                assert_eq!(expected, game.play_game());
+            }
+        )*
+        }
+    }
+
+    #[test]
+    fn reuses_slots_of_removed_marbles() {
+        for &num_marbles in &[23, 45, 46, 1618] {
+            let mut game = MarbleGame::new(9, num_marbles);
+            game.play_game();
+            assert!(game.marbles.slots() <= num_marbles + 2 - 2 * (num_marbles / 23));
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_games() {
+        assert_eq!((4, "expected `players;`".to_string()),
+                   parse_error::<MarbleGame>("10 elves; last marble is worth 1618 points"));
+        assert_eq!((33, "expected a number".to_string()),
+                   parse_error::<MarbleGame>("10 players; last marble is worth"));
+        assert_eq!((1, "expected a positive number".to_string()),
+                   parse_error::<MarbleGame>("0 players; last marble is worth 1618 points"));
+        assert_eq!((39, "expected `points`".to_string()),
+                   parse_error::<MarbleGame>("10 players; last marble is worth 1618 points: high score is 8317"));
+        assert_eq!((46, "unexpected text after game".to_string()),
+                   parse_error::<MarbleGame>("10 players; last marble is worth 1618 points each"));
+    }
+
+    #[test]
+    fn plays_every_game_of_input() {
+        let games = Games::parse("10 players; last marble is worth 1618 points\n\
+                                  13 players; last marble is worth 7999 points\n").unwrap();
+        let scores = games.part1().unwrap();
+        assert_eq!(vec![(10, 8317), (13, 146373)],
+                   scores.0.iter().map(|r| (r.players, r.high_score)).collect::<Vec<_>>());
+        assert_eq!("10 players; last marble is worth 1618 points: high score is 8317\n\
+                    13 players; last marble is worth 7999 points: high score is 146373",
+                   scores.to_string());
+        assert!(Games::parse("\n").is_err());
+    }
+
+    #[test]
+    fn ranks_all_players() {
+        let mut game = MarbleGame::new(9, 25);
+        game.record_history();
+        game.play_game();
+        let standings = game.scoreboard().standings().to_vec();
+        assert_eq!(Standing { rank: 1, player: 5, score: 32 }, standings[0]);
+        assert_eq!(Standing { rank: 2, player: 0, score: 0 }, standings[1]);
+        assert_eq!(Standing { rank: 2, player: 8, score: 0 }, standings[8]);
+        assert_eq!(&[ScoringEvent { round: 23, player: 5, placed: 23, removed: Some(9), points: 32 }],
+                   game.history());
+        assert_eq!((0, 0), MarbleGame::new(3, 5).play_game());
+    }
+
+    #[test]
+    fn limits_progress_reports() {
+        let mut reports = Vec::new();
+        MarbleGame::new(10, 1618).play_game_with(|played, total| reports.push((played, total)));
+        assert_eq!(Some(&(1618, 1618)), reports.last());
+        assert!(reports.len() <= 1001);
+        assert!(reports.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn plays_by_other_rules() {
+        assert_eq!(&Rules::default(), MarbleGame::new(9, 25).rules());
+
+        // The very first marble scores and empties the circle
+        let rules = Rules { starting_marble: 10, scoring_modulus: 11, ..Rules::default() };
+        let mut early: MarbleGame = MarbleGame::with_rules(9, 35, rules);
+        early.record_history();
+        early.play_game();
+        assert_eq!(ScoringEvent { round: 1, player: 1, placed: 11, removed: Some(10), points: 21 },
+                   early.history()[0]);
+        assert_eq!(3, early.history().len());
+
+        let rules = Rules { starting_marble: 10, ..Rules::default() };
+        let mut late: MarbleGame = MarbleGame::with_rules(9, 25, rules);
+        late.play_round();
+        assert_eq!("[1] 10(11)", late.render());
+
+        let rules = Rules { scoring_modulus: 0, insertion_offset: 0, ..Rules::default() };
+        let mut never_scores: MarbleGame = MarbleGame::with_rules(3, 50, rules);
+        assert_eq!((0, 0), never_scores.play_game());
+        assert_eq!(None, never_scores.play_round());
+    }
+
+    #[test]
+    fn traces_circle_like_puzzle() {
+        let mut lines = Vec::new();
+        MarbleGame::new(9, 25).trace(25, |line| lines.push(line.to_string()));
+        assert_eq!(26, lines.len());
+        assert_eq!("[-] (0)", lines[0]);
+        assert_eq!("[3]  0  2  1 (3)", lines[3]);
+        assert_eq!("[4]  0 16  8 17  4 18  9 19  2 20 10 21  5(22)11  1 12  6 13  3 14  7 15",
+                   lines[22]);
+        assert_eq!("[7]  0 16  8 17  4 18 19  2 24 20(25)10 21  5 22 11  1 12  6 13  3 14  7 15",
+                   lines[25]);
+
+        let mut deque_lines = Vec::new();
+        let mut game: MarbleGame<VecDeque<usize>> = MarbleGame::with_circle(9, 25);
+        game.trace(25, |line| deque_lines.push(line.to_string()));
+        assert_eq!(lines, deque_lines);
+    }
+
+    #[test]
+    fn plays_same_game_on_deque() {
+        let mut game: MarbleGame<VecDeque<usize>> = MarbleGame::with_circle(10, 1618);
+        assert_eq!((0, 8317), game.play_game());
+    }
+
+    marble_tests! {
+        game_00: ("9 players; last marble is worth 25 points", (5, 32)),
+        game_01: ("10 players; last marble is worth 1618 points", (0, 8317)),
+        game_02: ("13 players; last marble is worth 7999 points",  (12, 146373)),
+        game_03: ("17 players; last marble is worth 1104 points", (16, 2764)),
+        game_04: ("21 players; last marble is worth 6111 points", (5, 54718)),
+        game_05: ("30 players; last marble is worth 5807 points", (20, 37305)),
+    }
+}
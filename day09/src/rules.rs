@@ -0,0 +1,28 @@
+/// How the marbles are placed and when players score.
+///
+/// Offsets count marbles clockwise of the current marble, negative offsets
+/// count counter-clockwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// Marbles whose value is a multiple of this are kept by the player
+    /// instead of being placed, `0` means nobody ever scores
+    pub scoring_modulus: usize,
+    /// Offset of the marble that is removed when a player scores, the marble
+    /// clockwise of it becomes the current marble
+    pub removal_offset: isize,
+    /// Offset of the marble after which a new marble is placed, the placed
+    /// marble becomes the current marble
+    pub insertion_offset: isize,
+    /// Value of the marble in the circle before the first round, the first
+    /// marble played is the one after it
+    pub starting_marble: usize,
+}
+
+impl Default for Rules {
+    /// The puzzle's rules: every 23rd marble scores along with the marble 7
+    /// counter-clockwise, other marbles go between the marbles 1 and 2
+    /// clockwise of the current marble.
+    fn default() -> Rules {
+        Rules { scoring_modulus: 23, removal_offset: -7, insertion_offset: 1, starting_marble: 0 }
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt;
+
+/// Final score of a player and their place in the ranking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Standing {
+    /// 1-based place, shared by players with the same score
+    pub rank: usize,
+    pub player: usize,
+    pub score: usize,
+}
+
+/// Final scores of all players, ranked from the highest score down.
+///
+/// Players with the same score share a rank and are listed by their number,
+/// the next rank skips the places taken by them (`1, 2, 2, 4`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scoreboard {
+    standings: Vec<Standing>,
+}
+
+impl Scoreboard {
+    /// Rank the players by `scores`, which are indexed by player number.
+    pub fn new(scores: &[usize]) -> Scoreboard {
+        let mut players: Vec<usize> = (0..scores.len()).collect();
+        players.sort_by_key(|&player| (std::cmp::Reverse(scores[player]), player));
+        let mut standings: Vec<Standing> = Vec::with_capacity(players.len());
+        for (idx, &player) in players.iter().enumerate() {
+            let rank = match standings.last() {
+                Some(prev) if prev.score == scores[player] => prev.rank,
+                _ => idx + 1,
+            };
+            standings.push(Standing { rank, player, score: scores[player] });
+        }
+        Scoreboard { standings }
+    }
+
+    pub fn standings(&self) -> &[Standing] {
+        &self.standings
+    }
+
+    /// Player with the highest score, the lowest player number wins a tie.
+    pub fn winner(&self) -> Option<&Standing> {
+        self.standings.first()
+    }
+}
+
+impl fmt::Display for Scoreboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lines: Vec<String> = self.standings.iter()
+            .map(|s| format!("{:>3}. player {}: {}", s.rank, s.player, s.score))
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// A player keeping a marble and the one removed from the circle along with
+/// it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoringEvent {
+    /// 1-based round in which it happened
+    pub round: usize,
+    pub player: usize,
+    /// Marble the player kept instead of placing it
+    pub placed: usize,
+    /// Marble removed from the circle, `None` if the circle was empty
+    pub removed: Option<usize>,
+    pub points: usize,
+}
@@ -0,0 +1,197 @@
+use std::str::FromStr;
+use aoc_common::{err, parse_lines, parse_token, Error, Result, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+
+#[derive(Debug, Clone)]
+pub struct Star {
+    pub x: f64,
+    pub y: f64,
+    velocity: (f64, f64),
+}
+
+impl FromStr for Star {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Star> {
+        lazy_static! {
+            static ref POINT_RE: Regex = Regex::new(
+                r"^position=<\s*(?P<pos_x>[^,\s]+),\s*(?P<pos_y>[^>\s]+)> velocity=<\s*(?P<vel_x>[^,\s]+),\s*(?P<vel_y>[^>\s]+)>$").unwrap();
+        }
+        let caps = POINT_RE.captures(s)
+            .ok_or_else(|| Error::parse(0, s, "expected `position=<x, y> velocity=<x, y>`"))?;
+        let field = |name| {
+            let m = caps.name(name).unwrap();
+            parse_token::<i64>(m.as_str(), m.start()).map(|v| v as f64)
+        };
+        Ok(Star {
+            x: field("pos_x")?,
+            y: field("pos_y")?,
+            velocity: (field("vel_x")?, field("vel_y")?),
+        })
+    }
+}
+
+impl Star {
+    fn move_by(&mut self, delta_t: f64) {
+        let (vel_x, vel_y) = self.velocity;
+        self.x += vel_x * delta_t;
+        self.y += vel_y * delta_t;
+    }
+}
+
+#[derive(Clone)]
+pub struct Sky {
+    /// Seconds since the start, fractional while the viewer animates the sky
+    pub runtime: f64,
+    pub stars: Vec<Star>,
+}
+
+impl Sky {
+    pub fn new(stars: Vec<Star>) -> Sky {
+        Sky { runtime: 0.0, stars }
+    }
+
+    pub fn advance_by(&mut self, delta_t: f64) {
+        for star in &mut self.stars {
+            star.move_by(delta_t);
+        }
+        self.runtime += delta_t;
+        self.recenter();
+    }
+
+    fn recenter(&mut self) {
+        let (x0, y0, x1, y1) = self.viewport_boundaries();
+        let cx = (x1 + x0) / 2.0;
+        let cy = (y1 + y0) / 2.0;
+        for star in &mut self.stars {
+            star.x -= cx;
+            star.y -= cy;
+        }
+    }
+
+    fn viewport_boundaries(&self) -> (f64, f64, f64, f64) {
+        // We shouldn't have any NaNs, so we just unwrap
+        (self.stars.iter()
+            .map(|s| s.x).min_by(|a, b| a.partial_cmp(b).unwrap()).unwrap(),
+         self.stars.iter()
+            .map(|s| s.y).min_by(|a, b| a.partial_cmp(b).unwrap()).unwrap(),
+         self.stars.iter()
+            .map(|s| s.x).max_by(|a, b| a.partial_cmp(b).unwrap()).unwrap(),
+         self.stars.iter()
+            .map(|s| s.y).max_by(|a, b| a.partial_cmp(b).unwrap()).unwrap())
+    }
+
+    fn viewport(&self) -> (usize, usize) {
+        let (x0, y0, x1, y1) = self.viewport_boundaries();
+        ((x1 - x0) as usize, (y1 - y0) as usize)
+    }
+
+    pub fn forward_to_viewport(&mut self, width: usize, height: usize) {
+        while self.viewport() > (width, height) {
+            self.advance_by(1.0);
+        }
+    }
+
+    /// Find the message the stars spell out, which is when they are packed
+    /// as tightly as they will get.
+    ///
+    /// Width plus height of the stars' bounding box only shrinks until it
+    /// reaches its minimum, so the search stops once it no longer shrinks.
+    /// If all stars move alike, the box never changes and there is no
+    /// message to find.
+    pub fn find_message(&self) -> Result<Message> {
+        let velocity = self.stars[0].velocity;
+        if self.stars.iter().all(|star| star.velocity == velocity) {
+            return err!("The stars all move alike and never come together!");
+        }
+        let mut sky = self.clone();
+        let size = |(w, h): (usize, usize)| w + h;
+        let mut smallest = size(sky.viewport());
+        let mut seconds = 0;
+        loop {
+            sky.advance_by(1.0);
+            let current = size(sky.viewport());
+            if current >= smallest {
+                sky.advance_by(-1.0);
+                return Ok(Message { text: sky.render_text(), seconds });
+            }
+            smallest = current;
+            seconds += 1;
+        }
+    }
+
+    /// Render the current star positions as ASCII art, one `#` per star.
+    pub fn render_text(&self) -> String {
+        let (x0, y0, _, _) = self.viewport_boundaries();
+        let (width, height) = self.viewport();
+        let mut grid = vec![vec!['.'; width + 1]; height + 1];
+        for star in &self.stars {
+            let x = (star.x - x0).round() as usize;
+            let y = (star.y - y0).round() as usize;
+            grid[y][x] = '#';
+        }
+        grid.iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl FromStr for Sky {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Sky> {
+        let stars: Vec<Star> = parse_lines(s, str::parse)?;
+        if stars.is_empty() {
+            return err!("There must be at least one star!");
+        }
+        Ok(Sky::new(stars))
+    }
+}
+
+/// Message spelled out by the stars and the time it takes them to get there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub text: String,
+    pub seconds: u32,
+}
+
+impl Solution for Message {
+    type Part1Answer = String;
+    type Part2Answer = u32;
+
+    fn parse(input: &str) -> Result<Message> {
+        input.parse::<Sky>()?.find_message()
+    }
+
+    /// Message spelled out by the stars
+    fn part1(&self) -> Result<String> {
+        Ok(self.text.clone())
+    }
+
+    /// Seconds until the message appears
+    fn part2(&self) -> Result<u32> {
+        Ok(self.seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_stars_that_never_meet() {
+        assert!(Message::parse("position=< 1,  2> velocity=< 0,  0>").is_err());
+        assert!(Message::parse("position=< 1,  2> velocity=< 2, -1>\n\
+                                position=< 7,  4> velocity=< 2, -1>").is_err());
+    }
+
+    #[test]
+    fn finds_stars_meeting_on_a_line() {
+        let message = Message::parse("position=< 0,  0> velocity=< 1,  0>\n\
+                                      position=< 9,  0> velocity=<-1,  0>").unwrap();
+        assert_eq!(Message { text: "##".to_string(), seconds: 4 }, message);
+    }
+}